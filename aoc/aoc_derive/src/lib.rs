@@ -0,0 +1,201 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Attribute, Data, DeriveInput, Fields, GenericArgument, Ident, LitStr, PathArguments, Type};
+
+/// Options read from `#[input(...)]` on the struct or on one of its fields, with the span of the
+/// attribute that set them for errors.
+#[derive(Default)]
+struct InputAttrs {
+    sep: Option<(String, LitStr)>,
+    sections: Option<Ident>,
+    non_empty: Option<Ident>
+}
+
+fn parse_attrs(attrs: &[Attribute]) -> syn::Result<InputAttrs> {
+    let mut result = InputAttrs::default();
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("input")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("sep") {
+                let sep: LitStr = meta.value()?.parse()?;
+                result.sep = Some((sep.value(), sep));
+                Ok(())
+            } else if meta.path.is_ident("sections") {
+                result.sections = meta.path.get_ident().cloned();
+                Ok(())
+            } else if meta.path.is_ident("non_empty") {
+                result.non_empty = meta.path.get_ident().cloned();
+                Ok(())
+            } else {
+                Err(meta.error("expected `sep = \"...\"`, `sections` or `non_empty`"))
+            }
+        })?;
+    }
+    Ok(result)
+}
+
+/// Element type of a `Vec<T>` field, or `None` when the field is not a `Vec`.
+fn vec_element(ty: &Type) -> Option<&Type> {
+    let Type::Path(type_path) = ty else { return None };
+    let segment = type_path.path.segments.last()?;
+    if segment.ident != "Vec" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else { return None };
+    match args.args.first() {
+        Some(GenericArgument::Type(element)) => Some(element),
+        _ => None
+    }
+}
+
+/// Derives `aoc::parse::FromInput` for a struct.
+///
+/// By default the fields are read in order from a single line split on `#[input(sep = "...")]`
+/// (a space when omitted), the last field taking the rest of the line. `Vec<T>` fields are
+/// repeated and need their own `sep`. With `#[input(sections)]` each field is a `Vec<T>` read
+/// one item per line from the matching blank-line separated section. `#[input(non_empty)]` on a
+/// `Vec<T>` field rejects lines or sections that give it no items.
+#[proc_macro_derive(FromInput, attributes(input))]
+pub fn derive_from_input(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "FromInput can only be derived for structs"));
+    };
+    if data.fields.is_empty() {
+        return Err(syn::Error::new_spanned(&input.ident, "FromInput needs at least one field"));
+    }
+    let struct_attrs = parse_attrs(&input.attrs)?;
+    if let Some(non_empty) = &struct_attrs.non_empty {
+        return Err(syn::Error::new_spanned(non_empty, "`non_empty` goes on a `Vec<T>` field"));
+    }
+
+    let field_idents: Vec<_> = (0..data.fields.len()).map(|i| format_ident!("field_{}", i)).collect();
+    let n_fields = data.fields.len();
+    let sep: String = struct_attrs.sep.as_ref().map_or_else(|| " ".to_string(), |(sep, _)| sep.clone());
+    let mut field_parsers: Vec<TokenStream2> = vec![];
+
+    for (index, (field, ident)) in data.fields.iter().zip(field_idents.iter()).enumerate() {
+        let field_attrs = parse_attrs(&field.attrs)?;
+        let element = vec_element(&field.ty);
+        if let Some(sections) = &field_attrs.sections {
+            return Err(syn::Error::new_spanned(sections, "`sections` goes on the struct"));
+        }
+        if let (Some(non_empty), None) = (&field_attrs.non_empty, element) {
+            return Err(syn::Error::new_spanned(non_empty, "`non_empty` needs a `Vec<T>` field"));
+        }
+        let mut parser = if struct_attrs.sections.is_some() {
+            if let Some((_, sep)) = &field_attrs.sep {
+                return Err(syn::Error::new_spanned(sep, "`sep` has no effect in a `sections` struct"));
+            }
+            let Some(element) = element else {
+                return Err(syn::Error::new_spanned(&field.ty, "fields of a `sections` struct must be `Vec<T>`"));
+            };
+            quote! {
+                let #ident: Vec<#element> = ::aoc::parse::section_items(line, &sections[#index])?;
+            }
+        } else {
+            let value = match (element, &field_attrs.sep) {
+                (Some(element), Some((sep, _))) => quote! { ::aoc::parse::repeated::<#element>(line, part, #sep)? },
+                (Some(_), None) => {
+                    return Err(syn::Error::new_spanned(&field.ty, "repeated fields need `#[input(sep = \"...\")]`"));
+                }
+                (None, Some((_, sep))) => {
+                    return Err(syn::Error::new_spanned(sep, "`sep` on a field needs a `Vec<T>` field"));
+                }
+                (None, None) => {
+                    let ty = &field.ty;
+                    quote! { <#ty as ::aoc::parse::FromInput>::from_input(line, part)? }
+                }
+            };
+            quote! {
+                let part: &str = parts.next().ok_or_else(|| ::aoc::parse::ParseError::new(
+                    line, format!("expected {} fields separated by '{}'", #n_fields, #sep)
+                ))?;
+                let #ident = #value;
+            }
+        };
+        if field_attrs.non_empty.is_some() {
+            let what = field.ident.as_ref().map_or_else(|| format!("field {}", index), |ident| ident.to_string());
+            let message = format!("expected at least one value for {}", what);
+            parser.extend(quote! {
+                if #ident.is_empty() {
+                    return Err(::aoc::parse::ParseError::new(line, #message));
+                }
+            });
+        }
+        field_parsers.push(parser);
+    }
+
+    let prelude = if struct_attrs.sections.is_some() {
+        quote! {
+            let sections = ::aoc::parse::sections(text);
+            if sections.len() != #n_fields {
+                return Err(::aoc::parse::ParseError::new(line, format!("expected {} sections, found {}", #n_fields, sections.len())));
+            }
+        }
+    } else {
+        quote! {
+            let mut parts = text.splitn(#n_fields, #sep);
+        }
+    };
+
+    let construct = match &data.fields {
+        Fields::Named(fields) => {
+            let names = fields.named.iter().map(|field| field.ident.as_ref().unwrap());
+            quote! { #name { #(#names: #field_idents),* } }
+        }
+        _ => quote! { #name ( #(#field_idents),* ) }
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::aoc::parse::FromInput for #name #ty_generics #where_clause {
+            fn from_input(line: usize, text: &str) -> ::aoc::parse::ParseResult<Self> {
+                #prelude
+                #(#field_parsers)*
+                Ok(#construct)
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use syn::parse_quote;
+
+    fn error(input: DeriveInput) -> String {
+        expand(input).expect_err("expected a compile error").to_string()
+    }
+
+    #[test]
+    fn rejects_what_it_cannot_derive() {
+        assert_eq!(error(parse_quote! { enum Day { One } }), "FromInput can only be derived for structs");
+        assert_eq!(error(parse_quote! { struct Empty {} }), "FromInput needs at least one field");
+        assert_eq!(error(parse_quote! { struct Line { #[input(separator = ",")] values: Vec<u8> } }),
+                   "expected `sep = \"...\"`, `sections` or `non_empty`");
+    }
+
+    #[test]
+    fn rejects_misplaced_attributes() {
+        assert_eq!(error(parse_quote! { struct Line { values: Vec<u8> } }),
+                   "repeated fields need `#[input(sep = \"...\")]`");
+        assert_eq!(error(parse_quote! { struct Line { #[input(sep = ",")] value: u8 } }),
+                   "`sep` on a field needs a `Vec<T>` field");
+        assert_eq!(error(parse_quote! { struct Line { #[input(non_empty)] value: u8 } }),
+                   "`non_empty` needs a `Vec<T>` field");
+        assert_eq!(error(parse_quote! { #[input(non_empty)] struct Line { #[input(sep = ",")] values: Vec<u8> } }),
+                   "`non_empty` goes on a `Vec<T>` field");
+        assert_eq!(error(parse_quote! { struct Line { #[input(sections)] values: Vec<u8> } }),
+                   "`sections` goes on the struct");
+        assert_eq!(error(parse_quote! { #[input(sections)] struct Input { value: u8 } }),
+                   "fields of a `sections` struct must be `Vec<T>`");
+        assert_eq!(error(parse_quote! { #[input(sections)] struct Input { #[input(sep = ",")] values: Vec<u8> } }),
+                   "`sep` has no effect in a `sections` struct");
+    }
+}
@@ -0,0 +1,12 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// Errors are expected on arbitrary input; panics and hangs are bugs.
+fuzz_target!(|data: &[u8]| {
+    if let Ok(input) = std::str::from_utf8(data) {
+        let solution = aoc::days::solution(1).unwrap();
+        let _ = (solution.part_1)(input);
+        let _ = (solution.part_2)(input);
+    }
+});
@@ -0,0 +1,9 @@
+#!/bin/sh
+# Seeds the corpus of every target with the day's example input.
+set -e
+cd "$(dirname "$0")"
+for data in ../data/day_*; do
+    day=$(basename "$data")
+    mkdir -p "corpus/$day"
+    cp "$data/test.txt" "corpus/$day/test"
+done
@@ -0,0 +1,155 @@
+use std::fmt;
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::Instant;
+
+use crate::days::Solution;
+use crate::parse::ParseResult;
+
+/*
+    A small timing harness for the day solutions, run on the raw input so that parsing is included.
+    Statistics can be saved as a named baseline in a directory and later runs compared with it: a
+    part whose median moved by more than the noise threshold counts as faster or slower.
+*/
+
+/// Timings of one part, in nanoseconds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stats {
+    pub day: u8,
+    pub part: u8,
+    pub samples: usize,
+    pub median: u64,
+    pub min: u64,
+    pub max: u64
+}
+
+/// Times `part` of `solution` on `input` over `samples` runs, after one warm-up run.
+pub fn measure(solution: &Solution, part: u8, input: &str, samples: usize) -> ParseResult<Stats> {
+    let run = if part == 1 { solution.part_1 } else { solution.part_2 };
+    run(input)?;
+    let mut timings: Vec<u64> = (0..samples.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            let _ = black_box(run(black_box(input)));
+            start.elapsed().as_nanos() as u64
+        })
+        .collect();
+    timings.sort();
+    Ok(Stats {
+        day: solution.day,
+        part,
+        samples: timings.len(),
+        median: timings[timings.len() / 2],
+        min: timings[0],
+        max: timings[timings.len() - 1]
+    })
+}
+
+/// Path of the baseline `name` in `baseline_dir`.
+pub fn baseline_path(baseline_dir: &str, name: &str) -> String {
+    format!("{}/{}.tsv", baseline_dir, name)
+}
+
+const HEADER: &str = "day\tpart\tsamples\tmedian_ns\tmin_ns\tmax_ns";
+
+/// Writes `stats` as a tab-separated file with a header line.
+pub fn save_baseline(path: &str, stats: &[Stats]) -> Result<(), String> {
+    let mut text = format!("{}\n", HEADER);
+    for s in stats {
+        text += &format!("{}\t{}\t{}\t{}\t{}\t{}\n", s.day, s.part, s.samples, s.median, s.min, s.max);
+    }
+    if let Some(directory) = Path::new(path).parent() {
+        fs::create_dir_all(directory).map_err(|e| format!("couldn't create {}: {}", directory.display(), e))?;
+    }
+    fs::write(path, text).map_err(|e| format!("couldn't write {}: {}", path, e))
+}
+
+pub fn load_baseline(path: &str) -> Result<Vec<Stats>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))?;
+    text.lines()
+        .enumerate()
+        .skip(1)
+        .map(|(index, line)| {
+            let fields: Vec<u64> = line.split('\t')
+                .map(|field| field.parse::<u64>())
+                .collect::<Result<_, _>>()
+                .map_err(|e| format!("{}:{}: {}", path, index + 1, e))?;
+            match fields[..] {
+                [day, part, samples, median, min, max] => Ok(Stats {
+                    day: day as u8, part: part as u8, samples: samples as usize, median, min, max
+                }),
+                _ => Err(format!("{}:{}: expected 6 fields, found {}", path, index + 1, fields.len()))
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Change {
+    Faster,
+    Slower,
+    Unchanged
+}
+
+/// Relative change of the median in percent, and whether it is beyond `threshold` percent.
+pub fn compare(baseline: &Stats, current: &Stats, threshold: f64) -> (f64, Change) {
+    let percent = (current.median as f64 - baseline.median as f64) / baseline.median.max(1) as f64 * 100.0;
+    let change = if percent > threshold {
+        Change::Slower
+    } else if percent < -threshold {
+        Change::Faster
+    } else {
+        Change::Unchanged
+    };
+    (percent, change)
+}
+
+/// A duration given in nanoseconds, with a unit suited to its size.
+pub struct Duration(pub u64);
+
+impl fmt::Display for Duration {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            ns if ns < 1_000 => write!(f, "{} ns", ns),
+            ns if ns < 1_000_000 => write!(f, "{:.2} µs", ns as f64 / 1e3),
+            ns if ns < 1_000_000_000 => write!(f, "{:.2} ms", ns as f64 / 1e6),
+            ns => write!(f, "{:.2} s", ns as f64 / 1e9)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stats(day: u8, part: u8, median: u64) -> Stats {
+        Stats { day, part, samples: 10, median, min: median / 2, max: median * 2 }
+    }
+
+    #[test]
+    fn baselines_round_trip() {
+        let path = std::env::temp_dir().join(format!("aoc_bench_{}.tsv", std::process::id()));
+        let path = path.to_str().unwrap();
+        let all = vec![stats(6, 1, 1_500_000), stats(6, 2, 900_000_000)];
+        save_baseline(path, &all).unwrap();
+        assert_eq!(load_baseline(path).unwrap(), all);
+        fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn compare_applies_the_noise_threshold() {
+        let baseline = stats(7, 2, 1000);
+        assert_eq!(compare(&baseline, &stats(7, 2, 1040), 5.0), (4.0, Change::Unchanged));
+        assert_eq!(compare(&baseline, &stats(7, 2, 1200), 5.0), (20.0, Change::Slower));
+        assert_eq!(compare(&baseline, &stats(7, 2, 500), 5.0), (-50.0, Change::Faster));
+    }
+
+    #[test]
+    fn durations_pick_a_unit() {
+        assert_eq!(Duration(999).to_string(), "999 ns");
+        assert_eq!(Duration(1_500).to_string(), "1.50 µs");
+        assert_eq!(Duration(2_345_678).to_string(), "2.35 ms");
+        assert_eq!(Duration(3_000_000_000).to_string(), "3.00 s");
+    }
+}
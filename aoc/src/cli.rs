@@ -0,0 +1,81 @@
+use std::str::FromStr;
+
+/// Command line arguments split into positional arguments and `--name value` options.
+pub struct Args {
+    pub positional: Vec<String>,
+    options: Vec<(String, Option<String>)>
+}
+
+impl Args {
+    /// Parses `args`; options listed in `switches` take no value, every other option takes one,
+    /// either as the next argument or after an `=`.
+    pub fn parse(args: &[String], switches: &[&str]) -> Result<Args, String> {
+        let mut positional: Vec<String> = vec![];
+        let mut options: Vec<(String, Option<String>)> = vec![];
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if !arg.starts_with("--") {
+                positional.push(arg.to_string());
+            } else if let Some((name, value)) = arg.split_once('=') {
+                options.push((name.to_string(), Some(value.to_string())));
+            } else if switches.contains(&arg.as_str()) {
+                options.push((arg.to_string(), None));
+            } else {
+                let value = iter.next().ok_or_else(|| format!("missing value for {}", arg))?;
+                options.push((arg.to_string(), Some(value.to_string())));
+            }
+        }
+        Ok(Args { positional, options })
+    }
+
+    pub fn has(&self, name: &str) -> bool {
+        self.options.iter().any(|(n, _)| n == name)
+    }
+
+    /// Value of the last occurrence of option `name`.
+    pub fn value(&self, name: &str) -> Option<&str> {
+        self.options.iter().rev()
+            .find(|(n, _)| n == name)
+            .and_then(|(_, value)| value.as_deref())
+    }
+
+    /// Value of option `name` parsed as a `T`, or `default` when absent.
+    pub fn parsed_or<T: FromStr>(&self, name: &str, default: T) -> Result<T, String> {
+        match self.value(name) {
+            None => Ok(default),
+            Some(value) => value.parse().map_err(|_| format!("invalid value '{}' for {}", value, name))
+        }
+    }
+
+    /// Fails when more than one of the options `names` is given.
+    pub fn exclusive(&self, names: &[&str]) -> Result<(), String> {
+        let given: Vec<&str> = names.iter().copied().filter(|name| self.has(name)).collect();
+        match given.as_slice() {
+            [first, second, ..] => Err(format!("{} and {} can't be used together", first, second)),
+            _ => Ok(())
+        }
+    }
+
+    /// Positional argument `index` parsed as a `T`, `what` naming it in the error message.
+    pub fn positional<T: FromStr>(&self, index: usize, what: &str) -> Result<T, String> {
+        let value = self.positional.get(index).ok_or_else(|| format!("missing {}", what))?;
+        value.parse().map_err(|_| format!("invalid {} '{}'", what, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>(), &["--matrix"]).unwrap()
+    }
+
+    #[test]
+    fn exclusive_options() {
+        let modes = ["--matrix", "--report", "--removals"];
+        assert_eq!(args(&["run", "1", "--report", "csv"]).exclusive(&modes), Ok(()));
+        assert_eq!(args(&["run", "1", "--removals=2", "--matrix"]).exclusive(&modes),
+                   Err("--matrix and --removals can't be used together".to_string()));
+    }
+}
@@ -0,0 +1,238 @@
+/*
+    Lazy combinatorial iterators.
+
+    Each iterator keeps its state as a vector of indices into `items` and can be driven without
+    allocating through `next_indices`. The `Iterator` implementations clone the selected items into
+    a fresh `Vec` for convenience, in the same order as their `itertools` counterparts.
+*/
+
+/// Every sequence of length `k` drawn from `items` with repetition, i.e. `items^k`.
+pub struct CartesianPower<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    started: bool,
+    done: bool
+}
+
+pub fn cartesian_power<T>(items: &[T], k: usize) -> CartesianPower<'_, T> {
+    CartesianPower { items, indices: vec![0; k], started: false, done: items.is_empty() && k > 0 }
+}
+
+impl<'a, T> CartesianPower<'a, T> {
+    /// Advances to the next sequence and returns the indices of its items.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            return Some(&self.indices);
+        }
+        // Odometer: increment the last position, carrying to the left.
+        for position in (0..self.indices.len()).rev() {
+            self.indices[position] += 1;
+            if self.indices[position] < self.items.len() {
+                return Some(&self.indices);
+            }
+            self.indices[position] = 0;
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl<'a, T: Clone> Iterator for CartesianPower<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = self.items;
+        self.next_indices().map(|indices| indices.iter().map(|&i| items[i].clone()).collect())
+    }
+}
+
+/// Every way of picking `k` items out of `items`, keeping their original order.
+pub struct Combinations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    started: bool,
+    done: bool
+}
+
+pub fn combinations<T>(items: &[T], k: usize) -> Combinations<'_, T> {
+    Combinations { items, indices: (0..k).collect(), started: false, done: k > items.len() }
+}
+
+impl<'a, T> Combinations<'a, T> {
+    /// Advances to the next combination and returns the (increasing) indices of its items.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            return Some(&self.indices);
+        }
+        let n = self.items.len();
+        let k = self.indices.len();
+        // Rightmost position that can still move right, then reset everything after it.
+        match (0..k).rev().find(|&position| self.indices[position] < n - k + position) {
+            None => {
+                self.done = true;
+                None
+            }
+            Some(position) => {
+                self.indices[position] += 1;
+                for next in position + 1..k {
+                    self.indices[next] = self.indices[next - 1] + 1;
+                }
+                Some(&self.indices)
+            }
+        }
+    }
+}
+
+impl<'a, T: Clone> Iterator for Combinations<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = self.items;
+        self.next_indices().map(|indices| indices.iter().map(|&i| items[i].clone()).collect())
+    }
+}
+
+/// Every ordered selection of `k` distinct items out of `items`.
+pub struct Permutations<'a, T> {
+    items: &'a [T],
+    indices: Vec<usize>,
+    cycles: Vec<usize>,
+    k: usize,
+    started: bool,
+    done: bool
+}
+
+pub fn permutations<T>(items: &[T], k: usize) -> Permutations<'_, T> {
+    let n = items.len();
+    Permutations {
+        items,
+        indices: (0..n).collect(),
+        cycles: (0..k.min(n)).map(|i| n - i).collect(),
+        k,
+        started: false,
+        done: k > n
+    }
+}
+
+impl<'a, T> Permutations<'a, T> {
+    /// Advances to the next permutation and returns the indices of its items.
+    pub fn next_indices(&mut self) -> Option<&[usize]> {
+        if self.done {
+            return None;
+        }
+        if !self.started {
+            self.started = true;
+            return Some(&self.indices[..self.k]);
+        }
+        // Same cycle-based walk as Python's itertools.permutations, which yields lexicographic order.
+        let n = self.indices.len();
+        for position in (0..self.k).rev() {
+            self.cycles[position] -= 1;
+            if self.cycles[position] == 0 {
+                self.indices[position..].rotate_left(1);
+                self.cycles[position] = n - position;
+            } else {
+                let swap_with = n - self.cycles[position];
+                self.indices.swap(position, swap_with);
+                return Some(&self.indices[..self.k]);
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+impl<'a, T: Clone> Iterator for Permutations<'a, T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        let items = self.items;
+        self.next_indices().map(|indices| indices.iter().map(|&i| items[i].clone()).collect())
+    }
+}
+
+/// Every subset of size `k` of `{0, .., n - 1}` as a bit mask, in increasing order (Gosper's hack).
+pub struct KSubsets {
+    next: Option<u64>,
+    limit: u64
+}
+
+pub fn k_subsets(n: u32, k: u32) -> KSubsets {
+    assert!(n < 64, "k_subsets supports at most 63 elements, got {}", n);
+    let next = if k <= n { Some((1u64 << k) - 1) } else { None };
+    KSubsets { next, limit: 1u64 << n }
+}
+
+impl Iterator for KSubsets {
+    type Item = u64;
+
+    fn next(&mut self) -> Option<u64> {
+        let current = self.next?;
+        self.next = if current == 0 {
+            None
+        } else {
+            let lowest_bit = current & current.wrapping_neg();
+            let ripple = current + lowest_bit;
+            let candidate = (((ripple ^ current) >> 2) / lowest_bit) | ripple;
+            if candidate < self.limit { Some(candidate) } else { None }
+        };
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::Itertools;
+
+    #[test]
+    fn cartesian_power_matches_itertools() {
+        let items = ['+', '*', '|'];
+        for k in 1..5 {
+            let expected: Vec<Vec<char>> = std::iter::repeat_n(items.iter().copied(), k)
+                .multi_cartesian_product()
+                .collect();
+            assert_eq!(cartesian_power(&items, k).collect::<Vec<_>>(), expected);
+        }
+        assert_eq!(cartesian_power(&items, 0).count(), 1);
+        assert_eq!(cartesian_power::<char>(&[], 2).count(), 0);
+    }
+
+    #[test]
+    fn combinations_match_itertools() {
+        let items: Vec<i32> = (0..6).collect();
+        for k in 0..8 {
+            let expected: Vec<Vec<i32>> = items.iter().copied().combinations(k).collect();
+            assert_eq!(combinations(&items, k).collect::<Vec<_>>(), expected, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn permutations_match_itertools() {
+        let items: Vec<i32> = (0..5).collect();
+        for k in 0..7 {
+            let expected: Vec<Vec<i32>> = items.iter().copied().permutations(k).collect();
+            assert_eq!(permutations(&items, k).collect::<Vec<_>>(), expected, "k = {}", k);
+        }
+    }
+
+    #[test]
+    fn k_subsets_match_combinations() {
+        let items: Vec<u32> = (0..7).collect();
+        for k in 0..9 {
+            let mut expected: Vec<u64> = items.iter().copied().combinations(k as usize)
+                .map(|subset| subset.iter().map(|&i| 1u64 << i).sum())
+                .collect();
+            expected.sort();
+            assert_eq!(k_subsets(7, k).collect::<Vec<_>>(), expected, "k = {}", k);
+        }
+    }
+}
@@ -0,0 +1,477 @@
+use std::collections::BTreeMap;
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io::{self, IsTerminal};
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde::Deserialize;
+
+use crate::cli::Args;
+use crate::days::day_1::Delimiter;
+use crate::days::day_2::{Direction, SafetyPolicy};
+use crate::explain::Format;
+use crate::utils::input_path_in;
+
+/*
+    Project settings, read from `aoc.toml` in the current directory, or from the file named by
+    `--config` or `AOC_CONFIG`. Each setting `name` can be overridden by the environment variable
+    `AOC_NAME` and by the command line option `--name` (underscores becoming dashes): the command
+    line wins over the environment, which wins over the file, which wins over the defaults below.
+*/
+
+pub const CONFIG_FILE: &str = "aoc.toml";
+pub const DEFAULT_DATA_DIR: &str = "data";
+pub const DEFAULT_YEAR: u16 = 2024;
+pub const DEFAULT_INPUT: &str = "input";
+pub const DEFAULT_SESSION_TOKEN: &str = "env:AOC_SESSION";
+pub const DEFAULT_BASELINE_DIR: &str = "bench";
+
+/// Where a setting comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File,
+    Env,
+    Cli
+}
+
+/// Where to find the session token of the Advent of Code website.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TokenSource {
+    Env(String),
+    File(String)
+}
+
+impl TokenSource {
+    pub fn read(&self) -> Result<String, String> {
+        match self {
+            TokenSource::Env(name) => env::var(name).map_err(|_| format!("{} is not set", name)),
+            TokenSource::File(path) => fs::read_to_string(path)
+                .map(|token| token.trim().to_string())
+                .map_err(|e| format!("couldn't read {}: {}", path, e))
+        }
+    }
+}
+
+impl FromStr for TokenSource {
+    type Err = String;
+
+    /* The error doesn't repeat the value, in case a token was given instead of its source. */
+    fn from_str(s: &str) -> Result<TokenSource, String> {
+        match s.split_once(':') {
+            Some(("env", name)) if !name.is_empty() => Ok(TokenSource::Env(name.to_string())),
+            Some(("file", path)) if !path.is_empty() => Ok(TokenSource::File(path.to_string())),
+            _ => Err("expected env:NAME or file:PATH".to_string())
+        }
+    }
+}
+
+impl fmt::Display for TokenSource {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TokenSource::Env(name) => write!(f, "env:{}", name),
+            TokenSource::File(path) => write!(f, "file:{}", path)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Color {
+    Auto,
+    Always,
+    Never
+}
+
+impl FromStr for Color {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Color, String> {
+        match s {
+            "auto" => Ok(Color::Auto),
+            "always" => Ok(Color::Always),
+            "never" => Ok(Color::Never),
+            _ => Err(format!("unknown color '{}', expected auto, always or never", s))
+        }
+    }
+}
+
+impl fmt::Display for Color {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Color::Auto => write!(f, "auto"),
+            Color::Always => write!(f, "always"),
+            Color::Never => write!(f, "never")
+        }
+    }
+}
+
+pub const RED: &str = "31";
+pub const GREEN: &str = "32";
+
+/// Contents of `aoc.toml`, every setting being optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct File {
+    data_dir: Option<String>,
+    year: Option<u16>,
+    session_token: Option<String>,
+    input: Option<String>,
+    baseline_dir: Option<String>,
+    timeout: Option<u64>,
+    #[serde(default)]
+    timeouts: BTreeMap<String, u64>,
+    format: Option<String>,
+    color: Option<String>,
+    #[serde(default)]
+    day_1: Day1,
+    #[serde(default)]
+    day_2: Day2
+}
+
+/// The `[day_1]` section of `aoc.toml`, how day 1 reads its lists.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Day1 {
+    delimiter: Option<String>
+}
+
+/// The `[day_2]` section of `aoc.toml`, day 2's safety policy.
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Day2 {
+    min_step: Option<u32>,
+    max_step: Option<u32>,
+    direction: Option<String>,
+    min_level: Option<i32>,
+    max_level: Option<i32>
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Config {
+    /// The settings file that was read, if any.
+    pub path: Option<String>,
+    pub data_dir: String,
+    pub year: u16,
+    pub session_token: TokenSource,
+    /// Input variant used when a command isn't given one.
+    pub input: String,
+    /// Directory of the benchmark baselines.
+    pub baseline_dir: String,
+    /// Seconds a part may run for, 0 meaning no limit.
+    pub timeout: u64,
+    /// Timeouts of single days, from the file's `[timeouts]` table.
+    pub timeouts: BTreeMap<u8, u64>,
+    pub format: Format,
+    pub color: Color,
+    /// What separates day 1's location IDs, from the `[day_1]` section.
+    pub day_1: Delimiter,
+    /// Rules of day 2's safe reports, from the `[day_2]` section.
+    pub day_2: SafetyPolicy,
+    sources: BTreeMap<&'static str, Source>
+}
+
+/// Picks each setting from the highest-priority place that sets it, and records where it came from.
+struct Layers<'a, E: Fn(&str) -> Option<String>> {
+    path: &'a str,
+    env: E,
+    args: &'a Args,
+    sources: BTreeMap<&'static str, Source>
+}
+
+impl<E: Fn(&str) -> Option<String>> Layers<'_, E> {
+    fn get<T>(&mut self, key: &'static str, in_file: Option<String>,
+              parse: impl Fn(&str) -> Result<T, String>) -> Result<Option<T>, String> {
+        let env_name = format!("AOC_{}", key.to_uppercase());
+        let flag = format!("--{}", key.replace('_', "-"));
+        let (value, source, origin) = if let Some(value) = self.args.value(&flag) {
+            (value.to_string(), Source::Cli, flag)
+        } else if let Some(value) = (self.env)(&env_name) {
+            (value, Source::Env, env_name)
+        } else if let Some(value) = in_file {
+            (value, Source::File, self.path.to_string())
+        } else {
+            return Ok(None);
+        };
+        let value = parse(&value).map_err(|e| format!("invalid {} from {}: {}", key, origin, e))?;
+        self.sources.insert(key, source);
+        Ok(Some(value))
+    }
+}
+
+fn number<T: FromStr>(s: &str) -> Result<T, String> {
+    s.parse().map_err(|_| format!("expected a number, found '{}'", s))
+}
+
+impl Config {
+    /// Settings from the file, the environment and the command line `args`. A missing `aoc.toml`
+    /// leaves the defaults, but a missing file named by `--config` or `AOC_CONFIG` is an error.
+    pub fn load(args: &Args) -> Result<Config, String> {
+        let named = args.value("--config").map(str::to_string).or_else(|| env::var("AOC_CONFIG").ok());
+        let path = named.clone().unwrap_or_else(|| CONFIG_FILE.to_string());
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => Some(text),
+            Err(e) if named.is_none() && e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(format!("couldn't read {}: {}", path, e))
+        };
+        Config::resolve(text.as_deref().map(|text| (path.as_str(), text)), |name| env::var(name).ok(), args)
+    }
+
+    /// Settings from `file`, a path and its contents, the variables given by `env`, and `args`.
+    pub fn resolve(file: Option<(&str, &str)>, env: impl Fn(&str) -> Option<String>,
+                   args: &Args) -> Result<Config, String> {
+        let contents: File = match file {
+            None => File::default(),
+            Some((path, text)) => toml::from_str(text).map_err(|e| format!("{}: {}", path, e))?
+        };
+        let file_path = file.map_or(CONFIG_FILE, |(path, _)| path);
+        let mut timeouts: BTreeMap<u8, u64> = BTreeMap::new();
+        for (day, seconds) in &contents.timeouts {
+            match day.parse::<u8>() {
+                Ok(number) if (1..=25).contains(&number) => timeouts.insert(number, *seconds),
+                _ => return Err(format!("{}: timeouts: '{}' is not a day", file_path, day))
+            };
+        }
+
+        let mut layers = Layers {
+            path: file_path,
+            env,
+            args,
+            sources: BTreeMap::new()
+        };
+        let data_dir = layers.get("data_dir", contents.data_dir, |s| Ok(s.to_string()))?;
+        let year = layers.get("year", contents.year.map(|year| year.to_string()), number)?;
+        let session_token = layers.get("session_token", contents.session_token, TokenSource::from_str)?;
+        let input = layers.get("input", contents.input, |s| Ok(s.to_string()))?;
+        let baseline_dir = layers.get("baseline_dir", contents.baseline_dir, |s| Ok(s.to_string()))?;
+        let timeout = layers.get("timeout", contents.timeout.map(|seconds| seconds.to_string()), number)?;
+        let format = layers.get("format", contents.format, Format::from_str)?;
+        let color = layers.get("color", contents.color, Color::from_str)?;
+        let delimiter = layers.get("delimiter", contents.day_1.delimiter, Delimiter::from_str)?;
+        let day_2 = contents.day_2;
+        let min_step = layers.get("min_step", day_2.min_step.map(|step| step.to_string()), number)?;
+        let max_step = layers.get("max_step", day_2.max_step.map(|step| step.to_string()), number)?;
+        let direction = layers.get("direction", day_2.direction, Direction::from_str)?;
+        let min_level = layers.get("min_level", day_2.min_level.map(|level| level.to_string()), number)?;
+        let max_level = layers.get("max_level", day_2.max_level.map(|level| level.to_string()), number)?;
+        let puzzle = SafetyPolicy::PUZZLE;
+        let levels = (min_level.is_some() || max_level.is_some())
+            .then(|| (min_level.unwrap_or(i32::MIN), max_level.unwrap_or(i32::MAX)));
+        let day_2 = SafetyPolicy::new(min_step.unwrap_or(puzzle.min_step), max_step.unwrap_or(puzzle.max_step),
+                                      direction.unwrap_or(puzzle.direction), levels)
+            .map_err(|e| format!("invalid day 2 safety policy: {}", e))?;
+        Ok(Config {
+            path: file.map(|(path, _)| path.to_string()),
+            data_dir: data_dir.unwrap_or_else(|| DEFAULT_DATA_DIR.to_string()),
+            year: year.unwrap_or(DEFAULT_YEAR),
+            session_token: session_token.unwrap_or_else(|| DEFAULT_SESSION_TOKEN.parse().unwrap()),
+            input: input.unwrap_or_else(|| DEFAULT_INPUT.to_string()),
+            baseline_dir: baseline_dir.unwrap_or_else(|| DEFAULT_BASELINE_DIR.to_string()),
+            timeout: timeout.unwrap_or(0),
+            timeouts,
+            format: format.unwrap_or(Format::Text),
+            color: color.unwrap_or(Color::Auto),
+            day_1: delimiter.unwrap_or_default(),
+            day_2,
+            sources: layers.sources
+        })
+    }
+
+    pub fn source(&self, key: &str) -> Source {
+        self.sources.get(key).copied().unwrap_or(Source::Default)
+    }
+
+    /// Path of a day's input in the data directory, see `utils::input_path`.
+    pub fn input_path(&self, day: u8, variant: &str) -> String {
+        input_path_in(&self.data_dir, day, variant)
+    }
+
+    /// How long a part of `day` may run for. A timeout set in the environment or on the command
+    /// line applies to every day, otherwise one from the `[timeouts]` table comes first.
+    pub fn timeout(&self, day: u8) -> Option<Duration> {
+        let seconds = match self.source("timeout") {
+            Source::Env | Source::Cli => self.timeout,
+            Source::Default | Source::File => self.timeouts.get(&day).copied().unwrap_or(self.timeout)
+        };
+        (seconds > 0).then(|| Duration::from_secs(seconds))
+    }
+
+    /// Whether output to the terminal should be coloured, `NO_COLOR` turning off `auto`.
+    pub fn use_color(&self) -> bool {
+        match self.color {
+            Color::Always => true,
+            Color::Never => false,
+            Color::Auto => io::stdout().is_terminal() && env::var_os("NO_COLOR").is_none()
+        }
+    }
+
+    /// `text` in the ANSI colour `code` when colours are in use.
+    pub fn paint(&self, code: &str, text: &str) -> String {
+        if self.use_color() {
+            format!("\x1b[{}m{}\x1b[0m", code, text)
+        } else {
+            text.to_string()
+        }
+    }
+
+    fn origin(&self, key: &str) -> String {
+        match self.source(key) {
+            Source::Default => "default".to_string(),
+            Source::File => self.path.clone().unwrap_or_default(),
+            Source::Env => format!("AOC_{}", key.to_uppercase()),
+            Source::Cli => format!("--{}", key.replace('_', "-"))
+        }
+    }
+
+    /// The effective settings in the format of `aoc.toml`, each commented with where it comes from.
+    pub fn show(&self) -> String {
+        let mut text = match &self.path {
+            Some(path) => format!("# settings from {}\n", path),
+            None => format!("# no {} found\n", CONFIG_FILE)
+        };
+        let settings: [(&str, String); 8] = [
+            ("data_dir", format!("{:?}", self.data_dir)),
+            ("year", self.year.to_string()),
+            ("session_token", format!("{:?}", self.session_token.to_string())),
+            ("input", format!("{:?}", self.input)),
+            ("baseline_dir", format!("{:?}", self.baseline_dir)),
+            ("timeout", self.timeout.to_string()),
+            ("format", format!("{:?}", self.format.to_string())),
+            ("color", format!("{:?}", self.color.to_string()))
+        ];
+        for (key, value) in settings {
+            text += &format!("{} = {}  # {}\n", key, value, self.origin(key));
+        }
+        text += &format!("\n[day_1]\ndelimiter = {:?}  # {}\n", self.day_1.to_string(), self.origin("delimiter"));
+        text += "\n[day_2]\n";
+        let mut policy: Vec<(&str, String)> = vec![
+            ("min_step", self.day_2.min_step.to_string()),
+            ("max_step", self.day_2.max_step.to_string()),
+            ("direction", format!("{:?}", self.day_2.direction.to_string()))
+        ];
+        if let Some((low, high)) = self.day_2.levels {
+            policy.push(("min_level", low.to_string()));
+            policy.push(("max_level", high.to_string()));
+        }
+        for (key, value) in policy {
+            text += &format!("{} = {}  # {}\n", key, value, self.origin(key));
+        }
+        if !self.timeouts.is_empty() {
+            let overridden = matches!(self.source("timeout"), Source::Env | Source::Cli);
+            text += "\n[timeouts]\n";
+            for (day, seconds) in &self.timeouts {
+                text += &format!("{} = {}  # {}{}\n", day, seconds, self.path.as_deref().unwrap_or(CONFIG_FILE),
+                                 if overridden { ", overridden by timeout" } else { "" });
+            }
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<String>>(), &[]).unwrap()
+    }
+
+    fn no_env(_: &str) -> Option<String> {
+        None
+    }
+
+    const FILE: &str = "data_dir = \"puzzles\"\nyear = 2023\ninput = \"test\"\nformat = \"json\"\ntimeout = 60\n\n[timeouts]\n6 = 5\n";
+
+    #[test]
+    fn defaults_without_file() {
+        let config = Config::resolve(None, no_env, &args(&[])).unwrap();
+        assert_eq!(config.data_dir, "data");
+        assert_eq!(config.year, 2024);
+        assert_eq!(config.session_token, TokenSource::Env("AOC_SESSION".to_string()));
+        assert_eq!(config.input, "input");
+        assert_eq!(config.baseline_dir, "bench");
+        assert_eq!(config.input_path(3, "test"), "data/day_3/test.txt");
+        assert_eq!(config.timeout(6), None);
+        assert_eq!(config.source("year"), Source::Default);
+    }
+
+    #[test]
+    fn command_line_beats_environment_beats_file() {
+        let env = |name: &str| match name {
+            "AOC_DATA_DIR" => Some("from_env".to_string()),
+            "AOC_YEAR" => Some("2022".to_string()),
+            "AOC_INPUT" => Some("from_env".to_string()),
+            _ => None
+        };
+        let config = Config::resolve(Some(("aoc.toml", FILE)), no_env, &args(&[])).unwrap();
+        assert_eq!((config.year, config.source("year")), (2023, Source::File));
+        let config = Config::resolve(Some(("aoc.toml", FILE)), env, &args(&[])).unwrap();
+        assert_eq!((config.year, config.source("year")), (2022, Source::Env));
+        let config = Config::resolve(Some(("aoc.toml", FILE)), env, &args(&["run", "--year", "2021", "--input", "from_cli"])).unwrap();
+        assert_eq!((config.year, config.source("year")), (2021, Source::Cli));
+        assert_eq!((config.input.as_str(), config.source("input")), ("from_cli", Source::Cli));
+        assert_eq!((config.data_dir.as_str(), config.source("data_dir")), ("from_env", Source::Env));
+        assert_eq!((config.format, config.source("format")), (Format::Json, Source::File));
+        assert_eq!((config.color, config.source("color")), (Color::Auto, Source::Default));
+    }
+
+    #[test]
+    fn day_timeouts_apply_unless_overridden() {
+        let config = Config::resolve(Some(("aoc.toml", FILE)), no_env, &args(&[])).unwrap();
+        assert_eq!(config.timeout(6), Some(Duration::from_secs(5)));
+        assert_eq!(config.timeout(7), Some(Duration::from_secs(60)));
+        let config = Config::resolve(Some(("aoc.toml", FILE)), no_env, &args(&["--timeout", "0"])).unwrap();
+        assert_eq!(config.timeout(6), None);
+    }
+
+    #[test]
+    fn reports_bad_settings_with_their_origin() {
+        let error = |file: &str, args: &[&str]| Config::resolve(Some(("aoc.toml", file)), no_env, &self::args(args)).unwrap_err();
+        assert!(error("colour = \"never\"", &[]).contains("unknown field `colour`"));
+        assert_eq!(error("", &["--color", "blue"]),
+                   "invalid color from --color: unknown color 'blue', expected auto, always or never");
+        assert_eq!(error("session_token = \"53616c7465645f5f\"", &[]),
+                   "invalid session_token from aoc.toml: expected env:NAME or file:PATH");
+        assert_eq!(error("", &["--year", "twenty"]), "invalid year from --year: expected a number, found 'twenty'");
+        assert_eq!(error("[timeouts]\n26 = 1", &[]), "aoc.toml: timeouts: '26' is not a day");
+    }
+
+    #[test]
+    fn day_2_policy_from_its_section_and_options() {
+        let file = "[day_2]\nmax_step = 4\ndirection = \"increasing\"\nmin_level = 0\n";
+        let config = Config::resolve(Some(("aoc.toml", file)), no_env, &args(&["--min-step", "0"])).unwrap();
+        assert_eq!(config.day_2, SafetyPolicy::new(0, 4, Direction::Increasing, Some((0, i32::MAX))).unwrap());
+        assert_eq!(Config::resolve(None, no_env, &args(&[])).unwrap().day_2, SafetyPolicy::PUZZLE);
+        assert_eq!(Config::resolve(Some(("aoc.toml", file)), no_env, &args(&["--min-step", "5"])).unwrap_err(),
+                   "invalid day 2 safety policy: the smallest step 5 is above the largest one 4");
+        let shown = config.show();
+        assert!(shown.contains("[day_2]\nmin_step = 0  # --min-step\nmax_step = 4  # aoc.toml\n"));
+        let reread = Config::resolve(Some(("shown.toml", &shown)), no_env, &args(&[])).unwrap();
+        assert_eq!(reread.day_2, config.day_2);
+    }
+
+    #[test]
+    fn day_1_delimiter_from_its_section_and_option() {
+        let file = "[day_1]\ndelimiter = \",\"\n";
+        let config = Config::resolve(Some(("aoc.toml", file)), no_env, &args(&[])).unwrap();
+        assert_eq!(config.day_1, Delimiter::Exactly(",".to_string()));
+        assert!(config.show().contains("[day_1]\ndelimiter = \",\"  # aoc.toml\n"));
+        let config = Config::resolve(Some(("aoc.toml", file)), no_env, &args(&["--delimiter", "tab"])).unwrap();
+        assert_eq!((&config.day_1, config.source("delimiter")), (&Delimiter::Exactly("\t".to_string()), Source::Cli));
+        assert_eq!(Config::resolve(None, no_env, &args(&[])).unwrap().day_1, Delimiter::Whitespace);
+    }
+
+    #[test]
+    fn shows_where_settings_come_from() {
+        let config = Config::resolve(Some(("aoc.toml", FILE)), no_env, &args(&["--color", "never"])).unwrap();
+        let shown = config.show();
+        assert!(shown.starts_with("# settings from aoc.toml\ndata_dir = \"puzzles\"  # aoc.toml\n"));
+        assert!(shown.contains("year = 2023  # aoc.toml\nsession_token = \"env:AOC_SESSION\"  # default\n"));
+        assert!(shown.contains("input = \"test\"  # aoc.toml\nbaseline_dir = \"bench\"  # default\n"));
+        assert!(shown.contains("color = \"never\"  # --color\n"));
+        assert!(shown.ends_with("[timeouts]\n6 = 5  # aoc.toml\n"));
+        let reread = Config::resolve(Some(("shown.toml", &shown)), no_env, &args(&[])).unwrap();
+        assert_eq!((reread.data_dir, reread.timeouts, reread.color), (config.data_dir, config.timeouts, config.color));
+    }
+}
@@ -0,0 +1,112 @@
+use std::fmt;
+use std::fs;
+
+use crate::config::Config;
+use crate::days::{day_1, day_2, day_5, day_6, day_7};
+use crate::gen;
+use crate::parse::ParseResult;
+
+/*
+    Differential testing: when a part gets a faster implementation, the straightforward one is kept
+    as a reference and registered here. Both are run on the example, the real input and generated
+    inputs, and the first input where they disagree is reported.
+*/
+
+/// A reference and an optimized implementation of the same part, working on the raw input.
+pub struct Variant {
+    pub day: u8,
+    pub part: u8,
+    pub reference: fn(&str) -> ParseResult<i64>,
+    pub optimized: fn(&str) -> ParseResult<i64>
+}
+
+macro_rules! variant {
+    ($day:literal, $part:literal, $module:ident, $reference:ident, $optimized:ident) => {
+        Variant {
+            day: $day,
+            part: $part,
+            reference: |input| Ok($module::$reference(&$module::parse(input)?)),
+            optimized: |input| Ok($module::$optimized(&$module::parse(input)?))
+        }
+    };
+}
+
+pub const VARIANTS: [Variant; 6] = [
+    variant!(1, 2, day_1, part_2_reference, part_2),
+    variant!(2, 2, day_2, part_2_reference, part_2),
+    variant!(5, 2, day_5, part_2_reference, part_2),
+    variant!(6, 2, day_6, part_2_reference, part_2),
+    variant!(7, 1, day_7, part_1_reference, part_1),
+    variant!(7, 2, day_7, part_2_reference, part_2)
+];
+
+/// The registered variants of `day`, or of every day.
+pub fn variants(day: Option<u8>) -> impl Iterator<Item = &'static Variant> {
+    VARIANTS.iter().filter(move |variant| day.is_none_or(|day| variant.day == day))
+}
+
+/// An input to run both implementations on.
+pub struct Input {
+    pub name: String,
+    pub text: String
+}
+
+/// The example and real inputs of `day` that exist in the data directory of `config`.
+pub fn file_inputs(config: &Config, day: u8) -> Vec<Input> {
+    ["test", "input"].iter()
+        .map(|variant| config.input_path(day, variant))
+        .filter_map(|path| fs::read_to_string(&path).ok().map(|text| Input { name: path, text }))
+        .collect()
+}
+
+/// Inputs generated with `seeds` at the given size.
+pub fn generated_inputs(day: u8, seeds: impl IntoIterator<Item = u64>, size: usize) -> Vec<Input> {
+    seeds.into_iter()
+        .map(|seed| {
+            let mut bytes: Vec<u8> = vec![];
+            gen::generate(day, seed, size, &mut bytes).expect("writing to memory doesn't fail");
+            let text = String::from_utf8(bytes).expect("generated inputs are ASCII");
+            Input { name: format!("gen_{}_{}", seed, size), text }
+        })
+        .collect()
+}
+
+/// The first input on which the two implementations of a variant gave different results.
+#[derive(Debug)]
+pub struct Disagreement {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub reference: ParseResult<i64>,
+    pub optimized: ParseResult<i64>
+}
+
+impl fmt::Display for Disagreement {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let show = |result: &ParseResult<i64>| match result {
+            Ok(answer) => answer.to_string(),
+            Err(e) => format!("error ({})", e)
+        };
+        write!(f, "day {} part {} disagrees on {}: reference gives {}, optimized gives {}",
+               self.day, self.part, self.input, show(&self.reference), show(&self.optimized))
+    }
+}
+
+/// Runs both implementations on every input and returns the first disagreement, if any.
+pub fn check(variant: &Variant, inputs: &[Input]) -> Result<(), Disagreement> {
+    for input in inputs {
+        let reference = (variant.reference)(&input.text);
+        let optimized = (variant.optimized)(&input.text);
+        if reference != optimized {
+            return Err(Disagreement { day: variant.day, part: variant.part, input: input.name.clone(), reference, optimized });
+        }
+    }
+    Ok(())
+}
+
+/// Test helper: panics with the first disagreement between the implementations of `variant`.
+pub fn assert_agree(variant: &Variant, inputs: &[Input]) {
+    if let Err(disagreement) = check(variant, inputs) {
+        panic!("{}", disagreement);
+    }
+}
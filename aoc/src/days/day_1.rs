@@ -0,0 +1,1201 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, HashSet};
+use std::fmt;
+use std::fs::{self, File};
+use std::str::FromStr;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::iter::Peekable;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::order_statistics::OrderStatistics;
+use crate::diagnostics::{check_value, split_whitespace_with_columns, split_with_columns, Problem};
+use crate::parse::{self, Line, ParseError, ParseResult};
+
+/// Both location lists, each sorted.
+pub type Lists = (Vec<i64>, Vec<i64>);
+
+/// What separates the two location IDs of a line.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Any run of whitespace, such as the puzzle's three spaces or a tab.
+    #[default]
+    Whitespace,
+    /// Exactly this string, e.g. `,` for lists exported as CSV. IDs may be padded with whitespace.
+    Exactly(String)
+}
+
+impl FromStr for Delimiter {
+    type Err = String;
+
+    /// `whitespace`, `tab`, or any other string taken as it is.
+    fn from_str(s: &str) -> Result<Delimiter, String> {
+        match s {
+            "" => Err("expected whitespace, tab or a separator".to_string()),
+            "whitespace" => Ok(Delimiter::Whitespace),
+            "tab" => Ok(Delimiter::Exactly("\t".to_string())),
+            sep => Ok(Delimiter::Exactly(sep.to_string()))
+        }
+    }
+}
+
+impl fmt::Display for Delimiter {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Delimiter::Whitespace => write!(f, "whitespace"),
+            Delimiter::Exactly(sep) if sep == "\t" => write!(f, "tab"),
+            Delimiter::Exactly(sep) => write!(f, "{}", sep)
+        }
+    }
+}
+
+impl Delimiter {
+    /// The trimmed pieces of `text`, each with the column it starts at.
+    fn split<'a>(&self, text: &'a str) -> Vec<(usize, &'a str)> {
+        match self {
+            Delimiter::Whitespace => split_whitespace_with_columns(text),
+            Delimiter::Exactly(sep) => split_with_columns(text, sep).into_iter()
+                .map(|(column, piece)| {
+                    let padding = piece.chars().take_while(|c| c.is_whitespace()).count();
+                    (column + padding, piece.trim())
+                })
+                .collect()
+        }
+    }
+}
+
+/// `n` followed by "value" or "values".
+fn values(n: usize) -> String {
+    format!("{} value{}", n, if n == 1 { "" } else { "s" })
+}
+
+/// The `n_lists` location IDs of a line.
+fn parse_row(line: Line, delimiter: &Delimiter, n_lists: usize) -> ParseResult<Vec<i64>> {
+    let ids: ParseResult<Vec<i64>> = match delimiter {
+        Delimiter::Whitespace => line.words(),
+        Delimiter::Exactly(sep) => line.separated(sep)
+    };
+    match ids {
+        Ok(ids) if ids.len() == n_lists => Ok(ids),
+        // A wrong number of values explains a bad line better than the first value that didn't parse.
+        ids => {
+            let found = delimiter.split(line.text).len();
+            if found == n_lists {
+                return ids;
+            }
+            let expected = if n_lists == 2 { "two".to_string() } else { n_lists.to_string() };
+            Err(line.error(format!("expected {} location IDs, found {}", expected, values(found))))
+        }
+    }
+}
+
+fn parse_line(line: Line, delimiter: &Delimiter) -> ParseResult<(i64, i64)> {
+    let ids = parse_row(line, delimiter, 2)?;
+    Ok((ids[0], ids[1]))
+}
+
+pub fn parse(input: &str) -> ParseResult<Lists> {
+    parse_with(input, &Delimiter::Whitespace)
+}
+
+/// Parses lists whose IDs are separated by `delimiter`. Also rejects lists whose answers don't fit
+/// in 64 bits, which only happens with IDs far larger than the puzzle's, pointing at the line with
+/// the largest one.
+pub fn parse_with(input: &str, delimiter: &Delimiter) -> ParseResult<Lists> {
+    let (mut left, mut right) = read_lists(input, delimiter)?;
+    let largest = LargestId::of(&left, &right);
+
+    radix_sort(&mut left);
+    radix_sort(&mut right);
+
+    let lists = (left, right);
+    if !answers_fit(&lists) {
+        return Err(largest.overflow());
+    }
+    Ok(lists)
+}
+
+/// Both lists in the order of the input, as rank metrics need it.
+pub fn read_lists(input: &str, delimiter: &Delimiter) -> ParseResult<Lists> {
+    let mut left: Vec<i64> = vec![];
+    let mut right: Vec<i64> = vec![];
+    for line in parse::lines(input) {
+        let (a, b) = parse_line(line, delimiter)?;
+        left.push(a);
+        right.push(b);
+    }
+    Ok((left, right))
+}
+
+/// The ID farthest from 0 and the first line holding it, blamed when the answers overflow.
+#[derive(Debug, Clone, Copy, Default)]
+struct LargestId {
+    id: i64,
+    line: usize
+}
+
+impl LargestId {
+    fn of(left: &[i64], right: &[i64]) -> LargestId {
+        let mut largest = LargestId::default();
+        for (index, (&a, &b)) in left.iter().zip(right).enumerate() {
+            largest.add(index + 1, a);
+            largest.add(index + 1, b);
+        }
+        largest
+    }
+
+    fn add(&mut self, line: usize, id: i64) {
+        if self.line == 0 || id.unsigned_abs() > self.id.unsigned_abs() {
+            *self = LargestId { id, line };
+        }
+    }
+
+    fn overflow(&self) -> ParseError {
+        ParseError::new(self.line, format!("location ID {} is too large, the answers overflow 64 bits", self.id))
+    }
+}
+
+/// Sorts `values` with a least significant digit radix sort on bytes, skipping the bytes that all
+/// values share, e.g. the high ones of small IDs.
+pub fn radix_sort(values: &mut Vec<i64>) {
+    // Flipping the sign bit orders negative values before positive ones.
+    let key = |value: i64, shift: u32| (((value as u64) ^ (1 << 63)) >> shift) as usize & 0xff;
+    let mut buffer: Vec<i64> = vec![0; values.len()];
+    for shift in (0..64).step_by(8) {
+        let mut counts = [0usize; 256];
+        for &value in values.iter() {
+            counts[key(value, shift)] += 1;
+        }
+        if counts.contains(&values.len()) {
+            continue;
+        }
+        let mut offsets = [0usize; 256];
+        for digit in 1..256 {
+            offsets[digit] = offsets[digit - 1] + counts[digit - 1];
+        }
+        for &value in values.iter() {
+            let digit = key(value, shift);
+            buffer[offsets[digit]] = value;
+            offsets[digit] += 1;
+        }
+        std::mem::swap(values, &mut buffer);
+    }
+}
+
+/// Every line holds two location IDs separated by whitespace.
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = vec![];
+    for line in parse::lines(input) {
+        let ids = Delimiter::Whitespace.split(line.text);
+        if ids.len() != 2 {
+            problems.push(Problem::new(line.number, 0, format!("expected two location IDs, found {}", values(ids.len()))));
+        }
+        for (column, id) in ids {
+            if let Err(problem) = check_value::<i64>(line.number, column, id, "a location ID") {
+                problems.push(problem);
+            }
+        }
+    }
+    if let (true, Err(e)) = (problems.is_empty(), parse(input)) {
+        problems.push(Problem::new(e.line, 0, e.message));
+    }
+    problems
+}
+
+/// The lists in the puzzle's format, one pair per line.
+pub fn format((left, right): &Lists) -> String {
+    left.iter().zip(right.iter())
+        .map(|(a, b)| format!("{}   {}\n", a, b))
+        .collect()
+}
+
+/* Both answers are computed on 128 bits, which is enough for any lists of 64-bit IDs. */
+
+/// Sum of the distances between the IDs paired in order of two sorted lists.
+fn total_distance(left: impl Iterator<Item = i64>, right: impl Iterator<Item = i64>) -> i128 {
+    left.zip(right)
+        .map(|(a, b)| (a as i128 - b as i128).abs())
+        .sum()
+}
+
+/// Similarity of two sorted lists, merging them so that equal IDs are counted side by side.
+fn similarity_score(left: impl Iterator<Item = i64>, right: impl Iterator<Item = i64>) -> i128 {
+    let mut left = left.peekable();
+    let mut right = right.peekable();
+    let mut score = 0;
+    while let Some(id) = left.next() {
+        let mut n_left = 1;
+        while left.next_if_eq(&id).is_some() {
+            n_left += 1;
+        }
+        while right.next_if(|&other| other < id).is_some() {}
+        let mut n_right = 0;
+        while right.next_if_eq(&id).is_some() {
+            n_right += 1;
+        }
+        score += id as i128 * n_left * n_right;
+    }
+    score
+}
+
+/// `similarity_score` counting the right list's IDs in a map, the lists needing no order.
+fn similarity_score_by_counting((left, right): &Lists) -> i128 {
+    let mut right_map: HashMap<i64, i128> = HashMap::new();
+
+    let add_to_map = |i: &i64| {
+        match right_map.get(i) {
+            None => right_map.insert(*i, 1),
+            Some(count) => right_map.insert(*i, count+1)
+        };
+    };
+    right.iter().for_each(add_to_map);
+
+    left.iter().map(|i| match right_map.get(i) {
+        None => 0,
+        Some(count) => *count * *i as i128
+    }).sum()
+}
+
+fn answers_fit((left, right): &Lists) -> bool {
+    i64::try_from(total_distance(left.iter().copied(), right.iter().copied())).is_ok()
+        && i64::try_from(similarity_score(left.iter().copied(), right.iter().copied())).is_ok()
+}
+
+pub fn part_1((left, right): &Lists) -> i64 {
+    total_distance(left.iter().copied(), right.iter().copied())
+        .try_into().expect("parsing rejects lists whose answers overflow")
+}
+
+pub fn part_2((left, right): &Lists) -> i64 {
+    similarity_score(left.iter().copied(), right.iter().copied())
+        .try_into().expect("parsing rejects lists whose answers overflow")
+}
+
+/// `part_2` with a map of the right list's counts.
+pub fn part_2_reference(lists: &Lists) -> i64 {
+    similarity_score_by_counting(lists).try_into().expect("parsing rejects lists whose answers overflow")
+}
+
+/*
+    Any number of lists, one per column, all lines having as many columns as the first one. Each
+    pair of lists is compared as in the puzzle, and all of them with their consensus: the list
+    whose k-th smallest ID is the median of the lists' k-th smallest IDs (the lower median for an
+    even number of lists).
+*/
+
+/// Every list of a many-column input, each sorted.
+pub type ManyLists = Vec<Vec<i64>>;
+
+pub fn parse_many(input: &str, delimiter: &Delimiter) -> ParseResult<ManyLists> {
+    let mut lists: ManyLists = vec![];
+    for line in parse::lines(input) {
+        if lists.is_empty() {
+            lists = vec![vec![]; delimiter.split(line.text).len().max(1)];
+        }
+        let ids = parse_row(line, delimiter, lists.len())?;
+        for (list, id) in lists.iter_mut().zip(ids) {
+            list.push(id);
+        }
+    }
+    for list in lists.iter_mut() {
+        radix_sort(list);
+    }
+    Ok(lists)
+}
+
+/// Entry `[i][j]` is the total distance between lists `i` and `j`, as in part 1.
+pub fn distance_matrix(lists: &ManyLists) -> Vec<Vec<i128>> {
+    lists.iter()
+        .map(|a| lists.iter().map(|b| total_distance(a.iter().copied(), b.iter().copied())).collect())
+        .collect()
+}
+
+/// Entry `[i][j]` is the similarity of list `i` to list `j`, as in part 2 with `i` on the left.
+pub fn similarity_matrix(lists: &ManyLists) -> Vec<Vec<i128>> {
+    lists.iter()
+        .map(|a| lists.iter().map(|b| similarity_score(a.iter().copied(), b.iter().copied())).collect())
+        .collect()
+}
+
+/// The median of the lists' IDs of each rank.
+pub fn consensus(lists: &ManyLists) -> Vec<i64> {
+    let length = lists.iter().map(Vec::len).min().unwrap_or(0);
+    (0..length)
+        .map(|rank| {
+            let mut ids: Vec<i64> = lists.iter().map(|list| list[rank]).collect();
+            ids.sort();
+            ids[(ids.len() - 1) / 2]
+        })
+        .collect()
+}
+
+/// Total distance of every list to the consensus.
+pub fn consensus_distance(lists: &ManyLists) -> i128 {
+    let consensus = consensus(lists);
+    lists.iter().map(|list| total_distance(list.iter().copied(), consensus.iter().copied())).sum()
+}
+
+/// Both matrices with the lists numbered from 1, then the consensus distance.
+pub fn write_matrices(lists: &ManyLists, out: &mut impl Write) -> io::Result<()> {
+    for (name, matrix) in [("distance", distance_matrix(lists)), ("similarity", similarity_matrix(lists))] {
+        let width = matrix.iter().flatten().map(|value| value.to_string().len())
+            .chain([lists.len().to_string().len()])
+            .max().unwrap_or(1);
+        writeln!(out, "{}:", name)?;
+        let header: Vec<String> = (1..=lists.len()).map(|j| format!("{:>width$}", j)).collect();
+        writeln!(out, "{:>width$}  {}", "", header.join("  "))?;
+        for (i, row) in matrix.iter().enumerate() {
+            let cells: Vec<String> = row.iter().map(|value| format!("{:>width$}", value)).collect();
+            writeln!(out, "{:>width$}  {}", i + 1, cells.join("  "))?;
+        }
+    }
+    writeln!(out, "consensus distance: {}", consensus_distance(lists))
+}
+
+/*
+    Other ways of comparing the lists than the puzzle's, for lists of IDs coming from different
+    sources. Distances grow as the lists differ, while similarity and overlap grow as they agree.
+    The rank metrics compare the orders in which both lists hold the IDs they share, the k-th
+    occurrence of an ID in one list being paired with its k-th occurrence in the other.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ListMetric {
+    /// Sum of the distances between the IDs of the sorted lists, as in part 1.
+    Distance,
+    /// Sum of each left ID times its number of occurrences on the right, as in part 2.
+    Similarity,
+    /// Sum of the squared distances between the IDs of the sorted lists.
+    SquaredL2,
+    /// Sum of how far each shared ID moves between its ranks in both lists.
+    Footrule,
+    /// Number of pairs of shared IDs that the lists hold in opposite orders.
+    KendallTau,
+    /// Jaccard index of the lists as multisets: the size of their intersection over their union.
+    Jaccard
+}
+
+pub const LIST_METRICS: [ListMetric; 6] = [
+    ListMetric::Distance, ListMetric::Similarity, ListMetric::SquaredL2,
+    ListMetric::Footrule, ListMetric::KendallTau, ListMetric::Jaccard
+];
+
+impl fmt::Display for ListMetric {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ListMetric::Distance => write!(f, "distance"),
+            ListMetric::Similarity => write!(f, "similarity"),
+            ListMetric::SquaredL2 => write!(f, "squared-l2"),
+            ListMetric::Footrule => write!(f, "footrule"),
+            ListMetric::KendallTau => write!(f, "kendall-tau"),
+            ListMetric::Jaccard => write!(f, "jaccard")
+        }
+    }
+}
+
+impl FromStr for ListMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ListMetric, String> {
+        LIST_METRICS.iter()
+            .find(|metric| metric.to_string() == s)
+            .copied()
+            .ok_or_else(|| format!("unknown metric '{}', expected one of {}",
+                                   s, LIST_METRICS.map(|metric| metric.to_string()).join(", ")))
+    }
+}
+
+/// Value of a metric: a whole number, or a ratio for the Jaccard index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MetricValue {
+    Integer(i128),
+    Ratio(u64, u64)
+}
+
+impl fmt::Display for MetricValue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            MetricValue::Integer(value) => write!(f, "{}", value),
+            MetricValue::Ratio(numerator, 0) => write!(f, "{}/0", numerator),
+            MetricValue::Ratio(numerator, denominator) =>
+                write!(f, "{}/{} = {:.6}", numerator, denominator, numerator as f64 / denominator as f64)
+        }
+    }
+}
+
+impl ListMetric {
+    /// The metric on `left` and `right` in input order, or `None` if it doesn't fit in 128 bits.
+    pub fn measure(&self, left: &[i64], right: &[i64]) -> Option<MetricValue> {
+        let sorted = |list: &[i64]| {
+            let mut list = list.to_vec();
+            radix_sort(&mut list);
+            list
+        };
+        let (sorted_left, sorted_right) = (sorted(left), sorted(right));
+        let (sorted_left, sorted_right) = (sorted_left.iter().copied(), sorted_right.iter().copied());
+        let value = match self {
+            ListMetric::Distance => total_distance(sorted_left, sorted_right),
+            ListMetric::Similarity => similarity_score(sorted_left, sorted_right),
+            ListMetric::SquaredL2 => sorted_left.zip(sorted_right)
+                .try_fold(0i128, |sum, (a, b)| {
+                    let distance = i128::from(a.abs_diff(b));
+                    sum.checked_add(distance.checked_mul(distance)?)
+                })?,
+            ListMetric::Footrule => {
+                let ranks = shared_ranks(left, right);
+                ranks.iter().enumerate().map(|(rank, &other)| rank.abs_diff(other) as i128).sum()
+            }
+            ListMetric::KendallTau => count_inversions(&mut shared_ranks(left, right)) as i128,
+            ListMetric::Jaccard => {
+                let (shared, all) = multiset_overlap(sorted_left, sorted_right);
+                return Some(MetricValue::Ratio(shared, all));
+            }
+        };
+        Some(MetricValue::Integer(value))
+    }
+}
+
+/// For the IDs both lists hold, in the order of `left`, their ranks among those IDs in `right`.
+fn shared_ranks(left: &[i64], right: &[i64]) -> Vec<usize> {
+    let occurrences = |list: &[i64]| -> Vec<(i64, usize)> {
+        let mut seen: HashMap<i64, usize> = HashMap::new();
+        list.iter()
+            .map(|&id| {
+                let count = seen.entry(id).or_insert(0);
+                *count += 1;
+                (id, *count)
+            })
+            .collect()
+    };
+    let (left, right) = (occurrences(left), occurrences(right));
+    let in_left: HashSet<(i64, usize)> = left.iter().copied().collect();
+    let right_ranks: HashMap<(i64, usize), usize> = right.iter()
+        .filter(|occurrence| in_left.contains(occurrence))
+        .enumerate()
+        .map(|(rank, &occurrence)| (occurrence, rank))
+        .collect();
+    left.iter().filter_map(|occurrence| right_ranks.get(occurrence).copied()).collect()
+}
+
+/// Number of pairs out of order in `values`, counted while merge sorting them.
+fn count_inversions(values: &mut [usize]) -> u64 {
+    if values.len() < 2 {
+        return 0;
+    }
+    let middle = values.len() / 2;
+    let mut inversions = count_inversions(&mut values[..middle]) + count_inversions(&mut values[middle..]);
+    let mut merged: Vec<usize> = Vec::with_capacity(values.len());
+    let (mut i, mut j) = (0, middle);
+    while i < middle && j < values.len() {
+        if values[j] < values[i] {
+            // values[j] comes before every value left in the first half.
+            inversions += (middle - i) as u64;
+            merged.push(values[j]);
+            j += 1;
+        } else {
+            merged.push(values[i]);
+            i += 1;
+        }
+    }
+    merged.extend_from_slice(&values[i..middle]);
+    merged.extend_from_slice(&values[j..]);
+    values.copy_from_slice(&merged);
+    inversions
+}
+
+/// Sizes of the intersection and of the union of two sorted lists taken as multisets.
+fn multiset_overlap(left: impl Iterator<Item = i64>, right: impl Iterator<Item = i64>) -> (u64, u64) {
+    let (mut left, mut right) = (left.peekable(), right.peekable());
+    let (mut shared, mut all) = (0, 0);
+    loop {
+        let id = match (left.peek(), right.peek()) {
+            (None, None) => return (shared, all),
+            (Some(&a), Some(&b)) => a.min(b),
+            (Some(&a), None) => a,
+            (None, Some(&b)) => b
+        };
+        let (mut n_left, mut n_right) = (0, 0);
+        while left.next_if_eq(&id).is_some() {
+            n_left += 1;
+        }
+        while right.next_if_eq(&id).is_some() {
+            n_right += 1;
+        }
+        shared += n_left.min(n_right);
+        all += n_left.max(n_right);
+    }
+}
+
+/// How one ID contributes to the similarity score of part 2.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarityRow {
+    pub id: i64,
+    pub left_count: u64,
+    pub right_count: u64,
+    /// The ID times both counts, 0 unless it is in both lists.
+    pub contribution: i128
+}
+
+/// Breakdown of the similarity score by ID, over the IDs of both lists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SimilarityReport {
+    pub rows: Vec<SimilarityRow>
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportOrder {
+    /// By increasing ID.
+    Id,
+    /// By decreasing contribution, then by ID.
+    Contribution
+}
+
+impl FromStr for ReportOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ReportOrder, String> {
+        match s {
+            "id" => Ok(ReportOrder::Id),
+            "contribution" => Ok(ReportOrder::Contribution),
+            _ => Err(format!("unknown order '{}', expected id or contribution", s))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReportFormat {
+    Table,
+    Csv
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ReportFormat, String> {
+        match s {
+            "table" => Ok(ReportFormat::Table),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!("unknown report format '{}', expected table or csv", s))
+        }
+    }
+}
+
+/// Number of times the next value of `ids` occurs, consuming them.
+fn count_run(ids: &mut Peekable<impl Iterator<Item = i64>>, id: i64) -> u64 {
+    let mut count = 0;
+    while ids.next_if_eq(&id).is_some() {
+        count += 1;
+    }
+    count
+}
+
+pub fn similarity_report((left, right): &Lists) -> SimilarityReport {
+    let (mut left, mut right) = (left.iter().copied().peekable(), right.iter().copied().peekable());
+    let mut rows: Vec<SimilarityRow> = vec![];
+    while let Some(id) = match (left.peek(), right.peek()) {
+        (Some(&a), Some(&b)) => Some(a.min(b)),
+        (a, b) => a.or(b).copied()
+    } {
+        let (left_count, right_count) = (count_run(&mut left, id), count_run(&mut right, id));
+        let contribution = id as i128 * left_count as i128 * right_count as i128;
+        rows.push(SimilarityRow { id, left_count, right_count, contribution });
+    }
+    SimilarityReport { rows }
+}
+
+impl SimilarityReport {
+    pub fn sort(&mut self, order: ReportOrder) {
+        match order {
+            ReportOrder::Id => self.rows.sort_by_key(|row| row.id),
+            ReportOrder::Contribution => self.rows.sort_by_key(|row| (Reverse(row.contribution), row.id))
+        }
+    }
+
+    pub fn total(&self) -> i128 {
+        self.rows.iter().map(|row| row.contribution).sum()
+    }
+
+    /// IDs of the rows found only in the left list, or only in the right one.
+    pub fn only_in(&self, in_left: bool) -> Vec<i64> {
+        self.rows.iter()
+            .filter(|row| if in_left { row.right_count == 0 } else { row.left_count == 0 })
+            .map(|row| row.id)
+            .collect()
+    }
+
+    pub fn write(&self, format: ReportFormat, out: &mut impl Write) -> io::Result<()> {
+        match format {
+            ReportFormat::Table => self.write_table(out),
+            ReportFormat::Csv => self.write_csv(out)
+        }
+    }
+
+    /// Rows of IDs in both lists with aligned columns, the total, then the IDs found in one list.
+    fn write_table(&self, out: &mut impl Write) -> io::Result<()> {
+        let header = ["id", "left", "right", "contribution"];
+        let cells: Vec<[String; 4]> = self.rows.iter()
+            .filter(|row| row.left_count > 0 && row.right_count > 0)
+            .map(|row| [row.id.to_string(), row.left_count.to_string(), row.right_count.to_string(), row.contribution.to_string()])
+            .collect();
+        let widths: Vec<usize> = (0..4)
+            .map(|column| cells.iter().map(|row| row[column].len()).chain([header[column].len()]).max().unwrap_or(0))
+            .collect();
+        let line = |cells: [&str; 4]| -> String {
+            let padded: Vec<String> = cells.iter().zip(&widths).map(|(cell, &width)| format!("{:>width$}", cell)).collect();
+            padded.join("  ")
+        };
+        writeln!(out, "{}", line(header))?;
+        for row in &cells {
+            writeln!(out, "{}", line([&row[0], &row[1], &row[2], &row[3]]))?;
+        }
+        writeln!(out, "total: {}", self.total())?;
+        for (name, in_left) in [("left", true), ("right", false)] {
+            let ids: Vec<String> = self.only_in(in_left).iter().map(|id| id.to_string()).collect();
+            writeln!(out, "only in the {} list ({}): {}", name, ids.len(), ids.join(", "))?;
+        }
+        Ok(())
+    }
+
+    /// Every row with a header line, IDs found in one list having a count of 0 for the other.
+    fn write_csv(&self, out: &mut impl Write) -> io::Result<()> {
+        writeln!(out, "id,left_count,right_count,contribution")?;
+        for row in &self.rows {
+            writeln!(out, "{},{},{},{}", row.id, row.left_count, row.right_count, row.contribution)?;
+        }
+        Ok(())
+    }
+}
+
+/*
+    Lists kept up to date as IDs come and go, with their distance and similarity available at any
+    time. Each list is an order-statistic tree over its IDs, so an update costs O(64) whether or
+    not its ID was seen before, and nothing is ever sorted again.
+
+    Similarity changes by `id * count` on each update. Distance pairs the IDs of both lists by rank
+    and comes from the counts and sums kept in the trees, in O(64) per point where the sorted lists
+    cross rather than per ID.
+*/
+
+const LEFT: usize = 0;
+const RIGHT: usize = 1;
+
+#[derive(Debug, Clone, Default)]
+pub struct OnlineLists {
+    trees: [OrderStatistics; 2],
+    similarity: i128
+}
+
+impl OnlineLists {
+    pub fn new() -> OnlineLists {
+        OnlineLists::default()
+    }
+
+    /// Lists holding the IDs of `lists`.
+    pub fn from_lists((left, right): &Lists) -> OnlineLists {
+        let mut online = OnlineLists::new();
+        left.iter().for_each(|&id| online.insert_left(id));
+        right.iter().for_each(|&id| online.insert_right(id));
+        online
+    }
+
+    /// Adds or removes one occurrence of `id` in a list. Returns false when removing an absent ID.
+    fn update(&mut self, side: usize, id: i64, insert: bool) -> bool {
+        if insert {
+            self.trees[side].insert(id);
+        } else if !self.trees[side].remove(id) {
+            return false;
+        }
+        let change = id as i128 * self.trees[1 - side].count(id) as i128;
+        self.similarity += if insert { change } else { -change };
+        true
+    }
+
+    pub fn insert_left(&mut self, id: i64) {
+        self.update(LEFT, id, true);
+    }
+
+    pub fn insert_right(&mut self, id: i64) {
+        self.update(RIGHT, id, true);
+    }
+
+    /// Removes one occurrence of `id` from the left list, returning whether there was one.
+    pub fn remove_left(&mut self, id: i64) -> bool {
+        self.update(LEFT, id, false)
+    }
+
+    /// Removes one occurrence of `id` from the right list, returning whether there was one.
+    pub fn remove_right(&mut self, id: i64) -> bool {
+        self.update(RIGHT, id, false)
+    }
+
+    pub fn sizes(&self) -> (u64, u64) {
+        (self.trees[LEFT].len(), self.trees[RIGHT].len())
+    }
+
+    /// Total distance as in part 1, pairing the IDs of both lists by rank as far as the shorter goes.
+    pub fn distance(&self) -> i128 {
+        self.trees[LEFT].paired_distance(&self.trees[RIGHT])
+    }
+
+    /// Similarity score as in part 2.
+    pub fn similarity(&self) -> i128 {
+        self.similarity
+    }
+}
+
+/*
+    Streaming solver for lists too large to read into a string first. Lines are parsed one by one
+    and each list is kept in memory up to a limit of IDs; past it, the list is sorted in runs of
+    that many IDs written to temporary files, which are merged back when computing the answers.
+*/
+
+/// Error while solving from a reader: either reading failed or the input is invalid.
+#[derive(Debug)]
+pub enum StreamError {
+    Io(io::Error),
+    Parse(ParseError)
+}
+
+impl fmt::Display for StreamError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            StreamError::Io(e) => write!(f, "{}", e),
+            StreamError::Parse(e) => write!(f, "{}", e)
+        }
+    }
+}
+
+impl From<io::Error> for StreamError {
+    fn from(e: io::Error) -> StreamError {
+        StreamError::Io(e)
+    }
+}
+
+impl From<ParseError> for StreamError {
+    fn from(e: ParseError) -> StreamError {
+        StreamError::Parse(e)
+    }
+}
+
+/// IDs kept in memory per list by default before spilling sorted runs to disk, 128 MB each.
+pub const DEFAULT_MEMORY_LIMIT: usize = 1 << 24;
+
+/// Number of the next run written by this process, so that concurrent sorts never share a file.
+static NEXT_RUN: AtomicUsize = AtomicUsize::new(0);
+
+/// One list being read: the IDs in memory, and the sorted runs already written to disk, which are
+/// removed when it is dropped, also on errors.
+struct SpillingList {
+    name: &'static str,
+    ids: Vec<i64>,
+    limit: usize,
+    runs: Vec<PathBuf>
+}
+
+impl SpillingList {
+    fn new(name: &'static str, limit: usize) -> SpillingList {
+        SpillingList { name, ids: vec![], limit: limit.max(1), runs: vec![] }
+    }
+
+    fn push(&mut self, id: i64) -> io::Result<()> {
+        self.ids.push(id);
+        if self.ids.len() >= self.limit {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    fn spill(&mut self) -> io::Result<()> {
+        radix_sort(&mut self.ids);
+        let run = NEXT_RUN.fetch_add(1, Ordering::Relaxed);
+        let path = run_path(self.name, run);
+        let file = File::create_new(&path)?;
+        self.runs.push(path);
+        let mut out = BufWriter::new(file);
+        for id in &self.ids {
+            out.write_all(&id.to_le_bytes())?;
+        }
+        out.flush()?;
+        self.ids.clear();
+        Ok(())
+    }
+
+    /// Sorts what is left in memory, or writes it as a last run when some are on disk already.
+    fn finish(&mut self) -> io::Result<()> {
+        if self.runs.is_empty() {
+            radix_sort(&mut self.ids);
+        } else if !self.ids.is_empty() {
+            self.spill()?;
+        }
+        Ok(())
+    }
+
+    /// The list in sorted order, reading and merging the runs if there are any.
+    fn sorted(&self) -> io::Result<Sorted<'_>> {
+        if self.runs.is_empty() {
+            return Ok(Sorted::Memory(self.ids.iter()));
+        }
+        let mut readers: Vec<BufReader<File>> = self.runs.iter()
+            .map(|path| File::open(path).map(BufReader::new))
+            .collect::<io::Result<_>>()?;
+        let mut heads: BinaryHeap<Reverse<(i64, usize)>> = BinaryHeap::new();
+        for (index, reader) in readers.iter_mut().enumerate() {
+            if let Some(id) = read_id(reader)? {
+                heads.push(Reverse((id, index)));
+            }
+        }
+        Ok(Sorted::Runs { readers, heads, error: None })
+    }
+}
+
+impl Drop for SpillingList {
+    fn drop(&mut self) {
+        for path in &self.runs {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+fn run_path(name: &str, run: usize) -> PathBuf {
+    std::env::temp_dir().join(format!("aoc_day_1_{}_{}_{}.bin", std::process::id(), run, name))
+}
+
+fn read_id(reader: &mut impl Read) -> io::Result<Option<i64>> {
+    let mut bytes = [0u8; 8];
+    match reader.read_exact(&mut bytes) {
+        Ok(()) => Ok(Some(i64::from_le_bytes(bytes))),
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => Ok(None),
+        Err(e) => Err(e)
+    }
+}
+
+/// IDs of a list in sorted order. Merging runs stops at the first read error, kept in `error`.
+enum Sorted<'a> {
+    Memory(std::slice::Iter<'a, i64>),
+    Runs { readers: Vec<BufReader<File>>, heads: BinaryHeap<Reverse<(i64, usize)>>, error: Option<io::Error> }
+}
+
+impl Sorted<'_> {
+    fn error(self) -> io::Result<()> {
+        match self {
+            Sorted::Runs { error: Some(e), .. } => Err(e),
+            _ => Ok(())
+        }
+    }
+}
+
+impl Iterator for Sorted<'_> {
+    type Item = i64;
+
+    fn next(&mut self) -> Option<i64> {
+        match self {
+            Sorted::Memory(ids) => ids.next().copied(),
+            Sorted::Runs { readers, heads, error } => {
+                let Reverse((id, index)) = heads.pop()?;
+                match read_id(&mut readers[index]) {
+                    Ok(Some(next)) => heads.push(Reverse((next, index))),
+                    Ok(None) => {}
+                    Err(e) => {
+                        *error = Some(e);
+                        heads.clear();
+                    }
+                }
+                Some(id)
+            }
+        }
+    }
+}
+
+/// Solves both parts on lists read line by line from `reader`, keeping at most `memory_limit` IDs
+/// of each list in memory.
+pub fn solve_streaming(reader: impl BufRead, delimiter: &Delimiter, memory_limit: usize) -> Result<(i64, i64), StreamError> {
+    let mut left = SpillingList::new("left", memory_limit);
+    let mut right = SpillingList::new("right", memory_limit);
+    let mut reader = reader;
+    let mut text = String::new();
+    let mut number = 0;
+    let mut largest = LargestId::default();
+    loop {
+        text.clear();
+        if reader.read_line(&mut text)? == 0 {
+            break;
+        }
+        number += 1;
+        let (a, b) = parse_line(Line::new(number, text.trim_end_matches(['\n', '\r'])), delimiter)?;
+        largest.add(number, a);
+        largest.add(number, b);
+        left.push(a)?;
+        right.push(b)?;
+    }
+    left.finish()?;
+    right.finish()?;
+
+    let mut answers: [i64; 2] = [0; 2];
+    for (part, answer) in answers.iter_mut().enumerate() {
+        let (mut left_ids, mut right_ids) = (left.sorted()?, right.sorted()?);
+        let wide = if part == 0 {
+            total_distance(&mut left_ids, &mut right_ids)
+        } else {
+            similarity_score(&mut left_ids, &mut right_ids)
+        };
+        left_ids.error()?;
+        right_ids.error()?;
+        *answer = wide.try_into().map_err(|_| largest.overflow())?;
+    }
+    Ok((answers[0], answers[1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_accepts_any_whitespace() {
+        let expected: Lists = (vec![1, 3], vec![2, 4]);
+        assert_eq!(parse("3   4\n1   2\n").unwrap(), expected);
+        assert_eq!(parse("3\t4\n 1 2 \r\n").unwrap(), expected);
+    }
+
+    #[test]
+    fn parse_with_a_delimiter() {
+        let csv = Delimiter::Exactly(",".to_string());
+        assert_eq!(parse_with("3, 4\n1,2\n", &csv).unwrap(), (vec![1, 3], vec![2, 4]));
+        assert_eq!(parse_with("3 4\n", &csv).err().unwrap().message, "expected two location IDs, found 1 value");
+        assert_eq!(",".parse(), Ok(csv));
+        assert_eq!("tab".parse::<Delimiter>().map(|tab| parse_with("3\t4\n", &tab)), Ok(Ok((vec![3], vec![4]))));
+        assert_eq!(["whitespace", "tab", ";"].map(|name| name.parse::<Delimiter>().unwrap().to_string()), ["whitespace", "tab", ";"]);
+        assert!("".parse::<Delimiter>().is_err());
+    }
+
+    #[test]
+    fn parse_reports_the_offending_line() {
+        assert_eq!(parse("1 2\n3 4 5\n"), Err(parse::ParseError::new(2, "expected two location IDs, found 3 values")));
+        assert_eq!(parse("1 2\n3\n").err().unwrap().line, 2);
+        assert_eq!(parse("1 x\n").err().unwrap(), parse::ParseError::new(1, "invalid value 'x'"));
+    }
+
+    #[test]
+    fn supports_64_bit_ids() {
+        let lists = parse("5000000000 1\n5000000000 5000000000\n").unwrap();
+        assert_eq!(part_1(&lists), 4999999999);
+        assert_eq!(part_2(&lists), 10000000000);
+        let overflowing = format!("1 2\n{} 3\n4 {}\n", i64::MAX, i64::MIN);
+        assert_eq!(parse(&overflowing).err().unwrap(),
+                   parse::ParseError::new(3, format!("location ID {} is too large, the answers overflow 64 bits", i64::MIN)));
+        assert_eq!(check_input(&overflowing).len(), 1);
+        assert_eq!(check_input(&overflowing)[0].line, 3);
+        match solve_streaming(overflowing.as_bytes(), &Delimiter::Whitespace, 1) {
+            Err(StreamError::Parse(e)) => assert_eq!(e, parse(&overflowing).unwrap_err()),
+            other => panic!("expected a parse error, got {:?}", other)
+        }
+    }
+
+    #[test]
+    fn radix_sort_orders_negative_and_large_values() {
+        let mut values: Vec<i64> = vec![5, -3, i64::MAX, 0, i64::MIN, 70000, -70000, 5, 256];
+        let mut expected = values.clone();
+        expected.sort();
+        radix_sort(&mut values);
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn merged_similarity_matches_counting() {
+        let lists = parse("3 4\n4 3\n2 5\n1 3\n3 9\n3 3\n-2 -2\n").unwrap();
+        assert_eq!(part_2(&lists), part_2_reference(&lists));
+        assert_eq!(part_2(&lists), 31 - 2);
+    }
+
+    #[test]
+    fn streaming_spills_to_sorted_runs() {
+        let input = crate::cross_check::generated_inputs(1, [3], 1000).remove(0).text;
+        let lists = parse(&input).unwrap();
+        for limit in [1, 7, 1000, DEFAULT_MEMORY_LIMIT] {
+            let answers = solve_streaming(input.as_bytes(), &Delimiter::Whitespace, limit).unwrap();
+            assert_eq!(answers, (part_1(&lists), part_2(&lists)));
+        }
+        // Sorts running at the same time in one process write runs of their own.
+        let answers: Vec<(i64, i64)> = std::thread::scope(|scope| {
+            let handles: Vec<_> = (0..4)
+                .map(|_| scope.spawn(|| solve_streaming(input.as_bytes(), &Delimiter::Whitespace, 3).unwrap()))
+                .collect();
+            handles.into_iter().map(|handle| handle.join().unwrap()).collect()
+        });
+        assert!(answers.iter().all(|&answer| answer == (part_1(&lists), part_2(&lists))));
+        // A parse error after some runs were written removes them too.
+        match solve_streaming("1 2\n3 4\n5 6\n7\n".as_bytes(), &Delimiter::Whitespace, 1) {
+            Err(StreamError::Parse(e)) => assert_eq!(e.line, 4),
+            other => panic!("expected a parse error, got {:?}", other)
+        }
+        let prefix = format!("aoc_day_1_{}_", std::process::id());
+        let left_over = fs::read_dir(std::env::temp_dir()).unwrap()
+            .filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().starts_with(&prefix))
+            .count();
+        assert_eq!(left_over, 0);
+    }
+
+    #[test]
+    fn metrics_on_the_example() {
+        let (left, right) = read_lists(&crate::utils::read_contents("data/day_1/test.txt"), &Delimiter::Whitespace).unwrap();
+        let measure = |metric: ListMetric| metric.measure(&left, &right).unwrap();
+        assert_eq!(measure(ListMetric::Distance), MetricValue::Integer(11));
+        assert_eq!(measure(ListMetric::Similarity), MetricValue::Integer(31));
+        assert_eq!(measure(ListMetric::SquaredL2), MetricValue::Integer(35));
+        // The shared IDs are the three 3s and the 4, which the right list holds first.
+        assert_eq!(measure(ListMetric::Footrule), MetricValue::Integer(2));
+        assert_eq!(measure(ListMetric::KendallTau), MetricValue::Integer(1));
+        assert_eq!(measure(ListMetric::Jaccard), MetricValue::Ratio(4, 8));
+    }
+
+    #[test]
+    fn rank_metrics_of_reversed_lists() {
+        let left: Vec<i64> = (1..=6).collect();
+        let right: Vec<i64> = left.iter().rev().copied().collect();
+        assert_eq!(ListMetric::KendallTau.measure(&left, &right), Some(MetricValue::Integer(15)));
+        assert_eq!(ListMetric::Footrule.measure(&left, &right), Some(MetricValue::Integer(18)));
+        assert_eq!(ListMetric::Footrule.measure(&left, &left), Some(MetricValue::Integer(0)));
+    }
+
+    #[test]
+    fn squared_l2_reports_overflow() {
+        assert_eq!(ListMetric::SquaredL2.measure(&[i64::MIN, i64::MIN], &[i64::MAX, i64::MAX]), None);
+        assert_eq!("kendall-tau".parse(), Ok(ListMetric::KendallTau));
+        assert!("l2".parse::<ListMetric>().is_err());
+    }
+
+    #[test]
+    fn similarity_report_of_the_example() {
+        let mut report = similarity_report(&parse(&crate::utils::read_contents("data/day_1/test.txt")).unwrap());
+        assert_eq!(report.total(), 31);
+        assert_eq!((report.only_in(true), report.only_in(false)), (vec![1, 2], vec![5, 9]));
+        report.sort(ReportOrder::Contribution);
+        let mut table: Vec<u8> = vec![];
+        report.write(ReportFormat::Table, &mut table).unwrap();
+        assert_eq!(String::from_utf8(table).unwrap(), "\
+id  left  right  contribution
+ 3     3      3            27
+ 4     1      1             4
+total: 31
+only in the left list (2): 1, 2
+only in the right list (2): 5, 9
+");
+        report.sort(ReportOrder::Id);
+        let mut csv: Vec<u8> = vec![];
+        report.write(ReportFormat::Csv, &mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap().lines().take(3).collect::<Vec<&str>>(),
+                   ["id,left_count,right_count,contribution", "1,1,0,0", "2,1,0,0"]);
+    }
+
+    #[test]
+    fn many_lists_generalize_the_parts() {
+        let input = crate::utils::read_contents("data/day_1/test.txt");
+        let lists = parse(&input).unwrap();
+        let many = parse_many(&input, &Delimiter::Whitespace).unwrap();
+        assert_eq!(many, vec![lists.0.clone(), lists.1.clone()]);
+        assert_eq!(distance_matrix(&many)[0][1], part_1(&lists) as i128);
+        assert_eq!(similarity_matrix(&many)[0][1], part_2(&lists) as i128);
+    }
+
+    #[test]
+    fn consensus_of_three_lists() {
+        let many = parse_many("1 4 2\n5 2 9\n3 3 3\n", &Delimiter::Whitespace).unwrap();
+        assert_eq!(many, vec![vec![1, 3, 5], vec![2, 3, 4], vec![2, 3, 9]]);
+        assert_eq!(consensus(&many), vec![2, 3, 5]);
+        assert_eq!(consensus_distance(&many), 1 + 1 + 4);
+        assert_eq!(distance_matrix(&many), vec![vec![0, 2, 5], vec![2, 0, 5], vec![5, 5, 0]]);
+        assert_eq!(similarity_matrix(&many)[1], vec![3, 2 + 3 + 4, 2 + 3]);
+        let mut out: Vec<u8> = vec![];
+        write_matrices(&many, &mut out).unwrap();
+        assert!(String::from_utf8(out).unwrap().starts_with("distance:\n   1  2  3\n1  0  2  5\n"));
+        assert_eq!(parse_many("1 2 3\n4 5\n", &Delimiter::Whitespace).err().unwrap(),
+                   ParseError::new(2, "expected 3 location IDs, found 2 values"));
+    }
+
+    #[test]
+    fn online_lists_match_the_example() {
+        let lists = parse(&crate::utils::read_contents("data/day_1/test.txt")).unwrap();
+        let mut online = OnlineLists::from_lists(&lists);
+        assert_eq!((online.distance(), online.similarity()), (11, 31));
+        assert!(online.remove_right(9));
+        assert!(!online.remove_right(9));
+        assert!(!online.remove_left(7));
+        // The left list's 4 is now unpaired: 1 2 3 3 3 against 3 3 3 4 5.
+        assert_eq!((online.sizes(), online.distance()), ((6, 5), 6));
+        // Then 1 2 3 3 3 4 against -10 3 3 3 4 5.
+        online.insert_right(-10);
+        assert_eq!((online.distance(), online.similarity()), (14, 31));
+    }
+
+    #[test]
+    fn fresh_ids_only_add_their_paths() {
+        let mut online = OnlineLists::new();
+        // Every ID is new, and each lands between IDs already there.
+        let ids: Vec<i64> = (0..500).map(|i| (i * 7919) % 100_003 - 50_000).collect();
+        for &id in &ids {
+            let before = online.trees.each_ref().map(|tree| tree.node_count());
+            online.insert_left(id);
+            online.insert_right(-id);
+            let after = online.trees.each_ref().map(|tree| tree.node_count());
+            assert!((0..2).all(|side| after[side] - before[side] <= 64));
+        }
+        let (mut left, mut right) = (ids.clone(), ids.iter().map(|id| -id).collect::<Vec<_>>());
+        left.sort();
+        right.sort();
+        assert_eq!(online.distance(), part_1(&(left, right)) as i128);
+    }
+
+    #[derive(Debug, Clone)]
+    enum Operation {
+        Insert(usize, i64),
+        Remove(usize, i64)
+    }
+
+    fn operations() -> impl Strategy<Value = Vec<Operation>> {
+        // A few IDs far apart, so that gaps of very different widths show up.
+        let id = prop::sample::select(vec![-1_000_000, -3, 0, 1, 2, 5, 40, 41, 1_000_000_000_000]);
+        prop::collection::vec(
+            (any::<bool>(), 0..2usize, id).prop_map(|(insert, side, id)| {
+                if insert { Operation::Insert(side, id) } else { Operation::Remove(side, id) }
+            }),
+            0..80
+        )
+    }
+
+    proptest! {
+        #[test]
+        fn online_lists_agree_with_the_parts(ops in operations()) {
+            let mut online = OnlineLists::new();
+            let mut lists: [Vec<i64>; 2] = [vec![], vec![]];
+            for op in ops {
+                match op {
+                    Operation::Insert(side, id) => {
+                        if side == LEFT { online.insert_left(id) } else { online.insert_right(id) }
+                        lists[side].push(id);
+                    }
+                    Operation::Remove(side, id) => {
+                        let removed = if side == LEFT { online.remove_left(id) } else { online.remove_right(id) };
+                        let position = lists[side].iter().position(|&other| other == id);
+                        prop_assert_eq!(removed, position.is_some());
+                        if let Some(position) = position {
+                            lists[side].swap_remove(position);
+                        }
+                    }
+                }
+                let mut batch: Lists = (lists[LEFT].clone(), lists[RIGHT].clone());
+                batch.0.sort();
+                batch.1.sort();
+                prop_assert_eq!(online.distance(), part_1(&batch) as i128);
+                prop_assert_eq!(online.similarity(), part_2(&batch) as i128);
+            }
+        }
+    }
+
+    #[test]
+    fn check_input_points_at_columns() {
+        assert_eq!(check_input("1\t2\n3  x\n4\n"), vec![
+            Problem::new(2, 4, "expected a location ID, found 'x'"),
+            Problem::new(3, 0, "expected two location IDs, found 1 value")
+        ]);
+    }
+}
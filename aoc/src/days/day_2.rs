@@ -0,0 +1,627 @@
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+
+use crate::diagnostics::{check_value, split_with_columns, Problem};
+use crate::explain::Step;
+use crate::parse::{self, ParseResult};
+
+/// One report per line, each a list of levels.
+pub type Reports = Vec<Vec<i32>>;
+
+pub fn parse(input: &str) -> ParseResult<Reports> {
+    parse::lines(input)
+        .map(|line| line.separated(" "))
+        .collect()
+}
+
+/// Every line holds levels separated by single spaces.
+pub fn check_input(input: &str) -> Vec<Problem> {
+    parse::lines(input)
+        .flat_map(|line| split_with_columns(line.text, " ").into_iter()
+            .filter_map(move |(column, level)| check_value::<i32>(line.number, column, level, "a level").err()))
+        .collect()
+}
+
+/// The reports in the puzzle's format, one per line.
+pub fn format(reports: &Reports) -> String {
+    reports.iter()
+        .map(|levels| levels.iter().map(|level| level.to_string()).collect::<Vec<String>>().join(" ") + "\n")
+        .collect()
+}
+
+/// Which way the levels of a safe report go.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Increasing,
+    Decreasing,
+    /// All increasing or all decreasing, as in the puzzle.
+    Either,
+    /// All one way, with equal neighbours allowed whatever the smallest step.
+    NonStrict
+}
+
+impl Direction {
+    /// Signs of the steps of a safe report: 1 when increasing and -1 when decreasing.
+    fn signs(self) -> &'static [i64] {
+        match self {
+            Direction::Increasing => &[1],
+            Direction::Decreasing => &[-1],
+            Direction::Either | Direction::NonStrict => &[1, -1]
+        }
+    }
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Direction, String> {
+        match s {
+            "increasing" => Ok(Direction::Increasing),
+            "decreasing" => Ok(Direction::Decreasing),
+            "either" => Ok(Direction::Either),
+            "non-strict" => Ok(Direction::NonStrict),
+            _ => Err(format!("unknown direction '{}', expected increasing, decreasing, either or non-strict", s))
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Direction::Increasing => write!(f, "increasing"),
+            Direction::Decreasing => write!(f, "decreasing"),
+            Direction::Either => write!(f, "either"),
+            Direction::NonStrict => write!(f, "non-strict")
+        }
+    }
+}
+
+/// A rule of `SafetyPolicy` that a report can break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Rule {
+    DirectionChange,
+    StepTooSmall,
+    StepTooLarge,
+    OutOfRange
+}
+
+pub const RULES: [Rule; 4] = [Rule::DirectionChange, Rule::StepTooSmall, Rule::StepTooLarge, Rule::OutOfRange];
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Rule::DirectionChange => write!(f, "direction change"),
+            Rule::StepTooSmall => write!(f, "step too small"),
+            Rule::StepTooLarge => write!(f, "step too large"),
+            Rule::OutOfRange => write!(f, "level out of range")
+        }
+    }
+}
+
+/// The first place where a report breaks a rule: a pair of adjacent levels, or a single level out
+/// of range, starting at index `position`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Violation {
+    pub position: usize,
+    pub values: Vec<i32>,
+    pub rule: Rule
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let values: Vec<String> = self.values.iter().map(|value| value.to_string()).collect();
+        write!(f, "{} at index {} ({})", self.rule, self.position, values.join(" to "))
+    }
+}
+
+/// Outcome of `SafetyPolicy::check_report`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Verdict {
+    Safe,
+    /// Safe once the level at index `position` is removed, which fixes `violation`.
+    SafeWithout { position: usize, violation: Violation },
+    Unsafe(Violation)
+}
+
+/// Rules that the levels of a safe report follow.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyPolicy {
+    /// Smallest and largest change between adjacent levels, in the direction of the report.
+    pub min_step: u32,
+    pub max_step: u32,
+    pub direction: Direction,
+    /// Lowest and highest allowed level, if any.
+    pub levels: Option<(i32, i32)>
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> SafetyPolicy {
+        SafetyPolicy::PUZZLE
+    }
+}
+
+impl SafetyPolicy {
+    /// The puzzle's rules: all increasing or all decreasing, by 1 to 3.
+    pub const PUZZLE: SafetyPolicy = SafetyPolicy { min_step: 1, max_step: 3, direction: Direction::Either, levels: None };
+
+    pub fn new(min_step: u32, max_step: u32, direction: Direction, levels: Option<(i32, i32)>) -> Result<SafetyPolicy, String> {
+        if min_step > max_step {
+            return Err(format!("the smallest step {} is above the largest one {}", min_step, max_step));
+        }
+        if let Some((low, high)) = levels.filter(|(low, high)| low > high) {
+            return Err(format!("the lowest level {} is above the highest one {}", low, high));
+        }
+        Ok(SafetyPolicy { min_step, max_step, direction, levels })
+    }
+
+    fn allows_level(&self, level: i32) -> bool {
+        self.levels.is_none_or(|(low, high)| (low..=high).contains(&level))
+    }
+
+    /// Whether going from `a` to `b` is a step of an allowed size in the direction of `sign`, see
+    /// `Direction::signs`.
+    fn allows_step(&self, a: i32, b: i32, sign: i64) -> bool {
+        let step = (b as i64 - a as i64) * sign;
+        self.allows_flat(step) || (self.min_step as i64..=self.max_step as i64).contains(&step)
+    }
+
+    /// Whether `change` is a flat step that the direction allows below the smallest step.
+    fn allows_flat(&self, change: i64) -> bool {
+        change == 0 && self.direction == Direction::NonStrict
+    }
+
+    fn is_safe_in(&self, levels: &[i32], sign: i64) -> bool {
+        levels.iter().all(|&level| self.allows_level(level))
+            && levels.windows(2).all(|pair| self.allows_step(pair[0], pair[1], sign))
+    }
+
+    /// First position of a level whose removal makes `levels` safe in the direction of `sign`, in
+    /// O(n): removing level r works when the levels before it and those after it are safe, and its
+    /// neighbours make a safe step.
+    fn removable_in(&self, levels: &[i32], sign: i64) -> Option<usize> {
+        let n = levels.len();
+        // safe_from[i]: levels[i..] is safe.
+        let mut safe_from = vec![true; n + 1];
+        for i in (0..n).rev() {
+            safe_from[i] = safe_from[i + 1] && self.allows_level(levels[i])
+                && (i + 1 == n || self.allows_step(levels[i], levels[i + 1], sign));
+        }
+        let mut safe_before = true;
+        for r in 0..n {
+            let bridged = r == 0 || r + 1 == n || self.allows_step(levels[r - 1], levels[r + 1], sign);
+            if safe_before && bridged && safe_from[r + 1] {
+                return Some(r);
+            }
+            safe_before = safe_before && self.allows_level(levels[r])
+                && (r == 0 || self.allows_step(levels[r - 1], levels[r], sign));
+            if !safe_before {
+                return None;
+            }
+        }
+        None
+    }
+
+    /// Position of the first level whose removal makes `levels` safe.
+    fn removable_level(&self, levels: &[i32]) -> Option<usize> {
+        self.direction.signs().iter().filter_map(|&sign| self.removable_in(levels, sign)).min()
+    }
+
+    /// Length of the longest subsequence of `levels` that is safe in the direction of `sign`: the
+    /// longest one ending at each allowed level extends the best one ending at an earlier level it
+    /// can follow.
+    fn longest_safe_subsequence(&self, levels: &[i32], sign: i64) -> usize {
+        let mut longest_ending_at: Vec<usize> = Vec::with_capacity(levels.len());
+        for (i, &level) in levels.iter().enumerate() {
+            let longest = if self.allows_level(level) {
+                (0..i)
+                    .filter(|&j| longest_ending_at[j] > 0 && self.allows_step(levels[j], level, sign))
+                    .map(|j| longest_ending_at[j] + 1)
+                    .max()
+                    .unwrap_or(1)
+            } else {
+                0
+            };
+            longest_ending_at.push(longest);
+        }
+        longest_ending_at.into_iter().max().unwrap_or(0)
+    }
+
+    /// Fewest levels to remove to make the report safe, in O(n²).
+    pub fn min_removals(&self, levels: &[i32]) -> usize {
+        let longest = self.direction.signs().iter()
+            .map(|&sign| self.longest_safe_subsequence(levels, sign))
+            .max()
+            .unwrap_or(0);
+        levels.len() - longest
+    }
+
+    /// The first rule that `levels` breaks, scanning from the start. Under `Direction::Either` and
+    /// `Direction::NonStrict`, the first change of level sets the direction of the report.
+    pub fn first_violation(&self, levels: &[i32]) -> Option<Violation> {
+        let out_of_range = |position: usize| Violation { position, values: vec![levels[position]], rule: Rule::OutOfRange };
+        if levels.first().is_some_and(|&level| !self.allows_level(level)) {
+            return Some(out_of_range(0));
+        }
+        let mut sign = match self.direction {
+            Direction::Increasing => Some(1),
+            Direction::Decreasing => Some(-1),
+            Direction::Either | Direction::NonStrict => None
+        };
+        for (position, pair) in levels.windows(2).enumerate() {
+            let change = pair[1] as i64 - pair[0] as i64;
+            if sign.is_none() && change != 0 {
+                sign = Some(change.signum());
+            }
+            let violation = |rule| Some(Violation { position, values: pair.to_vec(), rule });
+            if change * sign.unwrap_or(0) < 0 {
+                return violation(Rule::DirectionChange);
+            }
+            if change.abs() < self.min_step as i64 && !self.allows_flat(change) {
+                return violation(Rule::StepTooSmall);
+            }
+            if change.abs() > self.max_step as i64 {
+                return violation(Rule::StepTooLarge);
+            }
+            if !self.allows_level(pair[1]) {
+                return Some(out_of_range(position + 1));
+            }
+        }
+        None
+    }
+
+    /// Whether `levels` is safe, safe once one level is removed, or else the first rule it breaks.
+    pub fn check_report(&self, levels: &[i32]) -> Verdict {
+        match self.first_violation(levels) {
+            None => Verdict::Safe,
+            Some(violation) => match self.removable_level(levels) {
+                Some(position) => Verdict::SafeWithout { position, violation },
+                None => Verdict::Unsafe(violation)
+            }
+        }
+    }
+
+    /// Whether removing at most `max_removals` levels makes the report safe.
+    pub fn is_safe(&self, levels: &[i32], max_removals: usize) -> bool {
+        if self.direction.signs().iter().any(|&sign| self.is_safe_in(levels, sign)) {
+            return true;
+        }
+        match max_removals {
+            0 => false,
+            1 => self.removable_level(levels).is_some(),
+            _ => self.min_removals(levels) <= max_removals
+        }
+    }
+}
+
+/// `SafetyPolicy::is_safe` under the puzzle's rules. Zero or one level cannot break any of them.
+pub fn is_safe(levels: &[i32], max_removals: usize) -> bool {
+    SafetyPolicy::PUZZLE.is_safe(levels, max_removals)
+}
+
+/// `SafetyPolicy::min_removals` under the puzzle's rules.
+pub fn min_removals(levels: &[i32]) -> usize {
+    SafetyPolicy::PUZZLE.min_removals(levels)
+}
+
+/// Number of reports made safe under `policy` by removing at most k levels, for each k up to
+/// `max_removals`.
+pub fn safe_counts(reports: &Reports, policy: &SafetyPolicy, max_removals: usize) -> Vec<i64> {
+    let mut counts: Vec<i64> = vec![0; max_removals + 1];
+    for levels in reports {
+        let needed = policy.min_removals(levels);
+        for count in counts.iter_mut().skip(needed) {
+            *count += 1;
+        }
+    }
+    counts
+}
+
+/// `SafetyPolicy::check_report` under the puzzle's rules.
+pub fn check_report(levels: &[i32]) -> Verdict {
+    SafetyPolicy::PUZZLE.check_report(levels)
+}
+
+/// Number of unsafe reports breaking a rule first, and how many of them one removal makes safe.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViolationCount {
+    pub rule: Rule,
+    pub reports: usize,
+    pub safe_without_one: usize
+}
+
+/// Unsafe reports under `policy` by the first rule they break, one count per rule of `RULES`.
+pub fn violation_summary(reports: &Reports, policy: &SafetyPolicy) -> Vec<ViolationCount> {
+    let mut counts: Vec<ViolationCount> = RULES.iter()
+        .map(|&rule| ViolationCount { rule, reports: 0, safe_without_one: 0 })
+        .collect();
+    for levels in reports {
+        let (violation, fixed) = match policy.check_report(levels) {
+            Verdict::Safe => continue,
+            Verdict::SafeWithout { violation, .. } => (violation, true),
+            Verdict::Unsafe(violation) => (violation, false)
+        };
+        let count = &mut counts[RULES.iter().position(|&rule| rule == violation.rule).unwrap()];
+        count.reports += 1;
+        count.safe_without_one += fixed as usize;
+    }
+    counts
+}
+
+/// The counts of `violation_summary` as a table with aligned columns, then their total.
+pub fn write_violation_summary(counts: &[ViolationCount], out: &mut impl Write) -> io::Result<()> {
+    let reports: usize = counts.iter().map(|count| count.reports).sum();
+    let safe_without_one: usize = counts.iter().map(|count| count.safe_without_one).sum();
+    let header = ["rule", "unsafe", "safe without one level"];
+    let cells: Vec<[String; 3]> = counts.iter()
+        .map(|count| [count.rule.to_string(), count.reports.to_string(), count.safe_without_one.to_string()])
+        .chain([["total".to_string(), reports.to_string(), safe_without_one.to_string()]])
+        .collect();
+    let widths: Vec<usize> = (0..3)
+        .map(|column| cells.iter().map(|row| row[column].len()).chain([header[column].len()]).max().unwrap_or(0))
+        .collect();
+    let line = |cells: [&str; 3]| -> String {
+        format!("{:<w0$}  {:>w1$}  {:>w2$}", cells[0], cells[1], cells[2], w0 = widths[0], w1 = widths[1], w2 = widths[2])
+    };
+    writeln!(out, "{}", line(header))?;
+    for row in &cells {
+        writeln!(out, "{}", line([&row[0], &row[1], &row[2]]))?;
+    }
+    Ok(())
+}
+
+/// `is_safe` with tolerance, trying every removal.
+fn is_safe_by_trying_every_removal(levels: &[i32]) -> bool {
+    is_safe(levels, 0) || (0..levels.len()).any(|position| {
+        let mut without_one = levels.to_vec();
+        without_one.remove(position);
+        is_safe(&without_one, 0)
+    })
+}
+
+/// Number of reports that are safe under `policy`.
+pub fn part_1_with(reports: &Reports, policy: &SafetyPolicy) -> i64 {
+    reports.iter().filter(|levels| policy.is_safe(levels, 0)).count() as i64
+}
+
+/// Number of reports that are safe under `policy` once one level is removed.
+pub fn part_2_with(reports: &Reports, policy: &SafetyPolicy) -> i64 {
+    reports.iter().filter(|levels| policy.is_safe(levels, 1)).count() as i64
+}
+
+pub fn part_1(reports: &Reports) -> i64 {
+    part_1_with(reports, &SafetyPolicy::PUZZLE)
+}
+
+pub fn part_2(reports: &Reports) -> i64 {
+    part_2_with(reports, &SafetyPolicy::PUZZLE)
+}
+
+/// `part_2` trying to remove every level in turn.
+pub fn part_2_reference(reports: &Reports) -> i64 {
+    reports.iter().filter(|levels| is_safe_by_trying_every_removal(levels)).count() as i64
+}
+
+/// Each safe report, along with the level removed to make it safe in part 2.
+pub fn explain(reports: &Reports, part: u8) -> Vec<Step> {
+    explain_with(reports, &SafetyPolicy::PUZZLE, part)
+}
+
+/// `explain` with the reports judged under `policy`.
+pub fn explain_with(reports: &Reports, policy: &SafetyPolicy, part: u8) -> Vec<Step> {
+    reports.iter().enumerate()
+        .filter_map(|(index, levels)| {
+            let report = levels.iter().map(|level| level.to_string()).collect::<Vec<String>>().join(" ");
+            let summary = format!("line {}: {}", index + 1, report);
+            if policy.is_safe(levels, 0) {
+                Some(Step::new(summary, vec![]))
+            } else if part == 2 {
+                policy.removable_level(levels).map(|position| Step::new(summary, vec![
+                    format!("safe without level {} at position {}", levels[position], position + 1)
+                ]))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_contents;
+    use std::ops::Range;
+
+    /// Every report of `len` levels taken from `levels`.
+    fn all_reports(len: u32, levels: Range<i32>) -> impl Iterator<Item = Vec<i32>> {
+        let base = levels.len();
+        (0..base.pow(len)).map(move |code| {
+            (0..len).map(|digit| levels.start + (code / base.pow(digit) % base) as i32).collect()
+        })
+    }
+
+    // test.txt is the published example (2 and 4 safe reports) followed by extra edge cases.
+    #[test]
+    fn published_example_reports() {
+        let reports = &parse(&read_contents("data/day_2/test.txt")).unwrap()[..6];
+        assert_eq!(reports.iter().filter(|levels| is_safe(levels, 0)).count(), 2);
+        assert_eq!(reports.iter().filter(|levels| is_safe(levels, 1)).count(), 4);
+    }
+
+    #[test]
+    fn is_safe_without_tolerance() {
+        assert!(is_safe(&[7, 6, 4, 2, 1], 0));
+        assert!(is_safe(&[1, 3, 6, 7, 9], 0));
+        assert!(!is_safe(&[1, 2, 7, 8, 9], 0));
+        assert!(!is_safe(&[1, 3, 2, 4, 5], 0));
+        assert!(!is_safe(&[8, 6, 4, 4, 1], 0));
+    }
+
+    #[test]
+    fn is_safe_tolerates_one_bad_level() {
+        assert!(is_safe(&[1, 3, 2, 4, 5], 1));
+        assert!(is_safe(&[8, 6, 4, 4, 1], 1));
+        assert!(is_safe(&[9, 1, 2, 3, 4], 1));
+        assert!(is_safe(&[1, 2, 3, 4, 9], 1));
+        assert!(!is_safe(&[1, 2, 7, 8, 9], 1));
+        assert!(!is_safe(&[9, 7, 6, 2, 1], 1));
+    }
+
+    #[test]
+    fn removal_at_either_end_or_in_the_middle() {
+        let removable_level = |levels: &[i32]| SafetyPolicy::PUZZLE.removable_level(levels);
+        assert_eq!(removable_level(&[5, 1, 2, 3]), Some(0));
+        assert_eq!(removable_level(&[1, 2, 3, 9]), Some(3));
+        assert_eq!(removable_level(&[1, 2, 2, 3]), Some(1));
+        // Removing the first level fixes the direction, which the level after the failure wouldn't.
+        assert_eq!(removable_level(&[3, 1, 2, 3, 4]), Some(0));
+        assert_eq!(removable_level(&[1, 5, 2, 3]), Some(1));
+        assert_eq!(removable_level(&[1, 2, 9, 10]), None);
+    }
+
+    #[test]
+    fn linear_check_agrees_with_trying_every_removal() {
+        // Every report of 5 levels between 0 and 4, which covers all shapes of small steps.
+        for levels in all_reports(5, 0..5) {
+            assert_eq!(is_safe(&levels, 1), is_safe_by_trying_every_removal(&levels), "{:?}", levels);
+            assert_eq!(is_safe(&levels, 1), min_removals(&levels) <= 1, "{:?}", levels);
+        }
+    }
+
+    #[test]
+    fn min_removals_by_longest_safe_subsequence() {
+        assert_eq!(min_removals(&[7, 6, 4, 2, 1]), 0);
+        assert_eq!(min_removals(&[1, 3, 2, 4, 5]), 1);
+        assert_eq!(min_removals(&[1, 2, 7, 8, 9]), 2);
+        assert_eq!(min_removals(&[9, 1, 8, 2, 7, 3]), 3);
+        assert_eq!(min_removals(&[4, 4, 4, 4]), 3);
+        assert!(is_safe(&[1, 2, 7, 8, 9], 2));
+        assert!(!is_safe(&[1, 2, 7, 8, 9], 1));
+    }
+
+    #[test]
+    fn safe_counts_grow_with_removals() {
+        let reports = parse(&read_contents("data/day_2/test.txt")).unwrap()[..6].to_vec();
+        assert_eq!(safe_counts(&reports, &SafetyPolicy::PUZZLE, 3), vec![2, 4, 6, 6]);
+    }
+
+    #[test]
+    fn policies_change_steps_directions_and_levels() {
+        let policy = |min_step, max_step, direction, levels| SafetyPolicy::new(min_step, max_step, direction, levels).unwrap();
+        let increasing = policy(1, 3, Direction::Increasing, None);
+        assert!(increasing.is_safe(&[1, 2, 4], 0));
+        assert!(!increasing.is_safe(&[4, 2, 1], 0));
+        assert_eq!(increasing.min_removals(&[9, 8, 1, 2]), 2);
+        let flat = policy(0, 2, Direction::Either, None);
+        assert!(flat.is_safe(&[8, 6, 6, 4], 0));
+        let non_strict = policy(1, 3, Direction::NonStrict, None);
+        assert!(non_strict.is_safe(&[1, 1, 3, 3, 4], 0));
+        assert!(non_strict.is_safe(&[6, 6, 6], 0));
+        assert!(!SafetyPolicy::PUZZLE.is_safe(&[1, 1, 3, 3, 4], 0));
+        assert!(!non_strict.is_safe(&[1, 3, 2, 4, 5], 0));
+        assert!(!non_strict.is_safe(&[1, 1, 5], 0));
+        assert_eq!(non_strict.min_removals(&[4, 4, 9, 5, 3, 3]), 2);
+        assert_eq!(non_strict.first_violation(&[2, 2, 3, 3, 1]),
+                   Some(Violation { position: 3, values: vec![3, 1], rule: Rule::DirectionChange }));
+        let bounded = policy(1, 3, Direction::Either, Some((0, 10)));
+        assert!(!bounded.is_safe(&[9, 10, 11], 0));
+        assert!(bounded.is_safe(&[9, 10, 11], 1));
+        assert!(!bounded.is_safe(&[-1], 0));
+        assert_eq!(bounded.min_removals(&[20, 1, 2, 30, 3]), 2);
+        assert_eq!(SafetyPolicy::new(3, 1, Direction::Either, None).unwrap_err(),
+                   "the smallest step 3 is above the largest one 1");
+        assert!(SafetyPolicy::new(1, 3, Direction::Either, Some((5, 4))).is_err());
+        assert_eq!("non-strict".parse::<Direction>(), Ok(Direction::NonStrict));
+    }
+
+    #[test]
+    fn bounded_levels_agree_with_trying_every_removal() {
+        let bounded = SafetyPolicy::new(0, 2, Direction::Either, Some((1, 3))).unwrap();
+        for levels in all_reports(5, 0..5) {
+            let by_trying = bounded.is_safe(&levels, 0) || (0..levels.len()).any(|position| {
+                let mut without_one = levels.clone();
+                without_one.remove(position);
+                bounded.is_safe(&without_one, 0)
+            });
+            assert_eq!(bounded.is_safe(&levels, 1), by_trying, "{:?}", levels);
+            assert_eq!(bounded.is_safe(&levels, 1), bounded.min_removals(&levels) <= 1, "{:?}", levels);
+        }
+    }
+
+    #[test]
+    fn check_report_finds_the_first_broken_rule() {
+        assert_eq!(check_report(&[7, 6, 4, 2, 1]), Verdict::Safe);
+        assert_eq!(check_report(&[1, 2, 7, 8, 9]),
+                   Verdict::Unsafe(Violation { position: 1, values: vec![2, 7], rule: Rule::StepTooLarge }));
+        assert_eq!(check_report(&[9, 7, 6, 6, 1]),
+                   Verdict::Unsafe(Violation { position: 2, values: vec![6, 6], rule: Rule::StepTooSmall }));
+        assert_eq!(check_report(&[1, 3, 2, 4, 5]), Verdict::SafeWithout {
+            position: 1,
+            violation: Violation { position: 1, values: vec![3, 2], rule: Rule::DirectionChange }
+        });
+        let bounded = SafetyPolicy::new(1, 3, Direction::Either, Some((0, 10))).unwrap();
+        assert_eq!(bounded.check_report(&[8, 9, 11]), Verdict::SafeWithout {
+            position: 2,
+            violation: Violation { position: 2, values: vec![11], rule: Rule::OutOfRange }
+        });
+        assert_eq!(Violation { position: 1, values: vec![2, 7], rule: Rule::StepTooLarge }.to_string(),
+                   "step too large at index 1 (2 to 7)");
+    }
+
+    #[test]
+    fn verdicts_agree_with_is_safe() {
+        let policies = [
+            SafetyPolicy::PUZZLE,
+            SafetyPolicy::new(0, 2, Direction::Increasing, Some((1, 3))).unwrap(),
+            SafetyPolicy::new(1, 2, Direction::NonStrict, None).unwrap()
+        ];
+        for policy in &policies {
+            for levels in all_reports(5, 0..5) {
+                let verdict = policy.check_report(&levels);
+                assert_eq!(verdict == Verdict::Safe, policy.is_safe(&levels, 0), "{:?}", levels);
+                assert_eq!(!matches!(verdict, Verdict::Unsafe(_)), policy.is_safe(&levels, 1), "{:?}", levels);
+            }
+        }
+    }
+
+    #[test]
+    fn violation_summary_of_the_example() {
+        let reports = parse(&read_contents("data/day_2/test.txt")).unwrap()[..6].to_vec();
+        let summary = violation_summary(&reports, &SafetyPolicy::PUZZLE);
+        let counts: Vec<(usize, usize)> = summary.iter().map(|count| (count.reports, count.safe_without_one)).collect();
+        assert_eq!(counts, [(1, 1), (1, 1), (2, 0), (0, 0)]);
+        let mut table: Vec<u8> = vec![];
+        write_violation_summary(&summary, &mut table).unwrap();
+        assert_eq!(String::from_utf8(table).unwrap(), "\
+rule                unsafe  safe without one level
+direction change         1                       1
+step too small           1                       1
+step too large           2                       0
+level out of range       0                       0
+total                    4                       2
+");
+    }
+
+    #[test]
+    fn explain_names_the_removed_level() {
+        let reports = parse(&read_contents("data/day_2/test.txt")).unwrap()[..6].to_vec();
+        assert_eq!(explain(&reports, 1).len(), 2);
+        let steps = explain(&reports, 2);
+        assert_eq!(steps.len(), 4);
+        assert_eq!(steps[1], Step::new("line 4: 1 3 2 4 5", vec!["safe without level 3 at position 2".to_string()]));
+        // With steps of 4 allowed, the third report is safe as it is.
+        let loose = SafetyPolicy::new(1, 4, Direction::Either, None).unwrap();
+        let steps = explain_with(&reports, &loose, 1);
+        assert_eq!(steps.len(), part_1_with(&reports, &loose) as usize);
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[1], Step::new("line 3: 9 7 6 2 1", vec![]));
+    }
+
+    #[test]
+    fn is_safe_handles_short_reports() {
+        assert!(is_safe(&[5], 0));
+        assert!(is_safe(&[5], 1));
+        assert!(is_safe(&[61, 635], 1));
+        assert!(!is_safe(&[i32::MAX, i32::MIN], 0));
+    }
+}
@@ -0,0 +1,89 @@
+use regex::Regex;
+
+use crate::diagnostics::Problem;
+use crate::parse::{self, Line, ParseResult};
+
+/// The uncorrupted instructions found in memory, in order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    Mul(i64, i64),
+    Do,
+    Dont
+}
+
+fn parse_mult(mult: &Line) -> ParseResult<Instruction> {
+    let operands: Vec<i64> = mult.ints()?;
+    if operands.len() != 2_usize {
+        Err(mult.error(format!("expected two operands in {}", mult.text)))
+    } else {
+        Ok(Instruction::Mul(operands[0], operands[1]))
+    }
+}
+
+pub fn parse(input: &str) -> ParseResult<Vec<Instruction>> {
+    let re = Regex::new(r"mul\([0-9]{1,3},[0-9]{1,3}\)|do\(\)|don't\(\)").unwrap();
+    parse::lines(input)
+        .flat_map(|line| re.find_iter(line.text).map(move |m| Line::new(line.number, m.as_str())))
+        .map(|elem| match elem.text {
+            "do()" => Ok(Instruction::Do),
+            "don't()" => Ok(Instruction::Dont),
+            _ => parse_mult(&elem)
+        })
+        .collect()
+}
+
+/// The memory is corrupted by design: anything that is not an instruction is skipped.
+pub fn check_input(_input: &str) -> Vec<Problem> {
+    vec![]
+}
+
+struct Acc {
+    should_multiply: bool,
+    sum: i64
+}
+
+pub fn part_1(instructions: &[Instruction]) -> i64 {
+    instructions.iter()
+        .map(|instruction| match instruction {
+            Instruction::Mul(left, right) => left * right,
+            _ => 0
+        })
+        .sum()
+}
+
+pub fn part_2(instructions: &[Instruction]) -> i64 {
+    let initial_acc = Acc { should_multiply: true, sum: 0 };
+
+    fn reduce(acc: Acc, elem: &Instruction) -> Acc {
+        match elem {
+            Instruction::Do => Acc { should_multiply: true, sum: acc.sum},
+            Instruction::Dont => Acc { should_multiply: false, sum: acc.sum},
+            Instruction::Mul(left, right) if acc.should_multiply => {
+                Acc { should_multiply: true, sum: acc.sum + left * right}
+            }
+            Instruction::Mul(_, _) => Acc { should_multiply: false, sum: acc.sum}
+        }
+    }
+
+    instructions.iter().fold(initial_acc, reduce).sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn products_are_not_truncated() {
+        let instructions = parse("mul(999,999)don't()mul(2,3)\ndo()mul(1,-1)mul(4,5)").unwrap();
+        assert_eq!(instructions, vec![Instruction::Mul(999, 999), Instruction::Dont, Instruction::Mul(2, 3),
+                                      Instruction::Do, Instruction::Mul(4, 5)]);
+        assert_eq!(part_1(&instructions), 998027);
+        assert_eq!(part_1(&[Instruction::Mul(i32::MAX as i64, 2)]), 4294967294);
+        assert_eq!(part_2(&instructions), 998021);
+    }
+
+    #[test]
+    fn mult_needs_two_operands() {
+        assert_eq!(parse_mult(&Line::new(4, "mul(7)")).unwrap_err().line, 4);
+    }
+}
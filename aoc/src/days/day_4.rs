@@ -0,0 +1,277 @@
+use core::fmt;
+
+use array2d::Array2D;
+use regex::Regex;
+
+use crate::diagnostics::{check_grid, Problem};
+use crate::parse::{ParseError, ParseResult};
+
+#[derive(PartialEq, Eq)]
+enum Chars {
+    X,
+    M,
+    A,
+    S,
+    Unknown
+}
+
+impl fmt::Debug for Chars {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Chars::X => write!(f, "X"),
+            Chars::M  => write!(f, "M"),
+            Chars::A  => write!(f, "A"),
+            Chars::S  => write!(f, "S"),
+            Chars::Unknown  => write!(f, "Unknown"),
+        }
+    }
+}
+
+fn get_char(c: &char) -> Chars {
+    if c.eq(&'X') {
+        Chars::X
+    } else if c.eq(&'M') {
+        Chars::M
+    } else if c.eq(&'A') {
+        Chars::A
+    } else if c.eq(&'S') {
+        Chars::S
+    } else {
+        Chars::Unknown
+    }
+}
+
+fn to_char(c: &Chars) -> char {
+    match c {
+        Chars::X => {'X'}
+        Chars::M => {'M'}
+        Chars::A => {'A'}
+        Chars::S => {'S'}
+        Chars::Unknown => {'_'}
+    }
+}
+
+fn build_diagonal_from_top_to_right_bottom(array: &Array2D<char>, start_col: &usize) -> Vec<Chars> {
+    let mut diagonal: Vec<Chars> = vec![];
+    for row in 0..array.num_rows() {
+        let col = start_col + row;
+        let item: Option<&char> = array.get(row, col);
+        match item {
+            None => { return diagonal; }
+            Some(c) => { diagonal.push(get_char(c)); }
+        }
+    }
+    diagonal
+}
+
+fn build_diagonal_from_top_to_left_bottom(array: &Array2D<char>, start_col: &usize) -> Vec<Chars> {
+    let mut diagonal: Vec<Chars> = vec![];
+    for row in 0..array.num_rows() {
+        if row > *start_col {
+            return diagonal;
+        }
+        let col = start_col - row;
+        let item: Option<&char> = array.get(row, col);
+        match item {
+            None => { return diagonal; }
+            Some(c) => { diagonal.push(get_char(c)); }
+        }
+    }
+    diagonal
+}
+
+fn build_diagonal_from_bottom_to_top_right(array: &Array2D<char>, start_col: &usize) -> Vec<Chars> {
+    let mut diagonal: Vec<Chars> = vec![];
+    for (offset, row) in (0..array.num_rows()).rev().enumerate() {
+        let item: Option<&char> = array.get(row, start_col + offset);
+        match item {
+            None => { return diagonal; }
+            Some(c) => { diagonal.push(get_char(c)); }
+        }
+    }
+    diagonal
+}
+
+fn build_diagonal_from_bottom_to_top_left(array: &Array2D<char>, start_col: &usize) -> Vec<Chars> {
+    let mut diagonal: Vec<Chars> = vec![];
+    let mut col: usize = *start_col;
+    for row in (0..array.num_rows()).rev() {
+        let item: Option<&char> = array.get(row, col);
+        match item {
+            None => { return diagonal; }
+            Some(c) => { diagonal.push(get_char(c)); }
+        }
+        if col == 0 {
+            return diagonal;
+        }
+        col -= 1;
+    }
+    diagonal
+}
+
+fn count_xmas(chars: &Vec<&Chars>) -> i64 {
+    let input: String = chars.iter().map(|c| to_char(c)).collect();
+    let re = Regex::new(r"XMAS").unwrap();
+    re.find_iter(&input).count() as i64
+}
+
+struct Window {
+    top_left: Chars, top_right: Chars,
+    center: Chars,
+    bottom_left: Chars, bottom_right: Chars
+}
+
+fn _get_from_array(array: &Array2D<char>, row_index: usize, col_index: usize) -> Chars {
+    get_char(array.get(row_index, col_index).unwrap())
+}
+
+fn build_window(array: &Array2D<char>, row_index: usize, col_index: usize) -> Window {
+    Window {
+        top_left: _get_from_array(array, row_index, col_index), top_right: _get_from_array(array, row_index, col_index+2),
+        center: _get_from_array(array, row_index+1, col_index+1),
+        bottom_left: _get_from_array(array, row_index+2, col_index), bottom_right: _get_from_array(array, row_index+2, col_index+2)
+    }
+}
+
+fn is_valid_mas_cross(window: Window) -> bool {
+    let pattern_1 = window.top_left == Chars::M && window.top_right == Chars::M && window.bottom_left == Chars::S && window.bottom_right == Chars::S;
+    let pattern_2 = window.top_left == Chars::S && window.top_right == Chars::M && window.bottom_left == Chars::S && window.bottom_right == Chars::M;
+    let pattern_3 = window.top_left == Chars::S && window.top_right == Chars::S && window.bottom_left == Chars::M && window.bottom_right == Chars::M;
+    let pattern_4 = window.top_left == Chars::M && window.top_right == Chars::S && window.bottom_left == Chars::M && window.bottom_right == Chars::S;
+
+    window.center == Chars::A
+    && (pattern_1 || pattern_2 || pattern_3 || pattern_4)
+}
+
+/// A rectangular grid of the letters X, M, A and S.
+pub fn check_input(input: &str) -> Vec<Problem> {
+    check_grid(input, |c| "XMAS".contains(c), "one of X, M, A, S").1
+}
+
+/// The word search as a grid of letters.
+pub fn parse(input: &str) -> ParseResult<Array2D<char>> {
+    let lines: Vec<Vec<char>> = input.lines().map(|s| s.chars().collect()).collect();
+    if lines.first().is_none_or(|line| line.is_empty()) {
+        return Err(ParseError::new(1, "the grid is empty"));
+    }
+    if let Some(index) = lines.iter().position(|line| line.len() != lines[0].len()) {
+        return Err(ParseError::new(index + 1, format!(
+            "row has {} letters while the first one has {}", lines[index].len(), lines[0].len()
+        )));
+    }
+    Ok(Array2D::from_rows(&lines).expect("rows have the same length"))
+}
+
+/// The grid in the puzzle's format, one row per line.
+pub fn format(array: &Array2D<char>) -> String {
+    array.rows_iter()
+        .map(|row| row.collect::<String>())
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+pub fn part_1(array: &Array2D<char>) -> i64 {
+    let mut sequences: Vec<Vec<Chars>> = vec![];
+    // horizontal sequences
+    for row_iter in array.rows_iter() {
+        let row: Vec<Chars> = row_iter.map(get_char).collect();
+        sequences.push(row);
+    }
+    // vertical sequences
+    for col_iter in array.columns_iter() {
+        let col: Vec<Chars> = col_iter.map(get_char).collect();
+        sequences.push(col);
+    }
+    // diagonals
+    for i in 0..array.num_columns() {
+        let diagonal_to_right_bottom = build_diagonal_from_top_to_right_bottom(array, &{ i });
+        sequences.push(diagonal_to_right_bottom);
+        let diagonal_to_left_bottom = build_diagonal_from_top_to_left_bottom(array, &{ i });
+        sequences.push(diagonal_to_left_bottom);
+    }
+    // Start from 1 to avoid counting the main diagonal twice
+    for i in 1..array.num_columns() {
+        let diagonal_to_top_right = build_diagonal_from_bottom_to_top_right(array, &{ i });
+        sequences.push(diagonal_to_top_right);
+    }
+    // End 1 before num columns to avoid counting the other main diagonal twice
+    for i in 0..array.num_columns()-1 {
+        let diagonal_to_top_left = build_diagonal_from_bottom_to_top_left(array, &{ i });
+        sequences.push(diagonal_to_top_left);
+    }
+
+    let reversed_sequences: Vec<Vec<&Chars>> = sequences.iter()
+        .map(|seq| seq.iter().rev().collect())
+        .collect();
+
+    let mut all_sequences: Vec<Vec<&Chars>> = vec![];
+    reversed_sequences.iter().for_each(|rev_seq| {
+        all_sequences.push(rev_seq.to_vec())
+    });
+    sequences.iter().for_each(|seq| {
+        all_sequences.push(seq.iter().collect());
+    });
+
+    all_sequences.iter()
+        .filter(|seq| seq.len() > 3)
+        .map(count_xmas)
+        .sum()
+}
+
+pub fn part_2(array: &Array2D<char>) -> i64 {
+    let n_rows: usize = array.num_rows();
+    let n_cols: usize = array.num_columns();
+
+    let mut count_valid: i64 = 0;
+    for row_index in 0..n_rows.saturating_sub(2) {
+        for col_index in 0..n_cols.saturating_sub(2) {
+            let window: Window = build_window(array, row_index, col_index);
+            if is_valid_mas_cross(window) {
+                count_valid += 1;
+            }
+        }
+    }
+    count_valid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /*
+        XMA
+        SXM
+        ASX
+    */
+    fn small_array() -> Array2D<char> {
+        Array2D::from_rows(&[vec!['X', 'M', 'A'], vec!['S', 'X', 'M'], vec!['A', 'S', 'X']]).unwrap()
+    }
+
+    #[test]
+    fn diagonals_from_top() {
+        let array = small_array();
+        assert_eq!(build_diagonal_from_top_to_right_bottom(&array, &0), vec![Chars::X, Chars::X, Chars::X]);
+        assert_eq!(build_diagonal_from_top_to_right_bottom(&array, &1), vec![Chars::M, Chars::M]);
+        assert_eq!(build_diagonal_from_top_to_left_bottom(&array, &2), vec![Chars::A, Chars::X, Chars::A]);
+        assert_eq!(build_diagonal_from_top_to_left_bottom(&array, &0), vec![Chars::X]);
+    }
+
+    #[test]
+    fn diagonals_from_bottom() {
+        let array = small_array();
+        assert_eq!(build_diagonal_from_bottom_to_top_right(&array, &0), vec![Chars::A, Chars::X, Chars::A]);
+        assert_eq!(build_diagonal_from_bottom_to_top_right(&array, &1), vec![Chars::S, Chars::M]);
+        assert_eq!(build_diagonal_from_bottom_to_top_left(&array, &2), vec![Chars::X, Chars::X, Chars::X]);
+        assert_eq!(build_diagonal_from_bottom_to_top_left(&array, &1), vec![Chars::S, Chars::S]);
+    }
+
+    #[test]
+    fn grids_smaller_than_a_cross() {
+        for input in ["MM", "X\nM\nA\nS", "XMAS"] {
+            let grid = parse(input).unwrap();
+            assert_eq!(part_2(&grid), 0);
+        }
+        assert_eq!(part_1(&parse("XMAS").unwrap()), 1);
+        assert!(parse("").is_err());
+    }
+}
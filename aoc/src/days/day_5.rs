@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+
+use crate::diagnostics::{check_value, split_with_columns, Problem};
+use crate::explain::Step;
+use crate::graph::DiGraph;
+use crate::parse::{self, FromInput, ParseResult};
+use crate::FromInput;
+
+/// Page ordering rules, with an edge `before -> after` for every rule.
+pub type Rules = DiGraph<i32>;
+
+/// The ordering rules and the pages of every update.
+pub type PrintQueue = (Rules, Vec<Vec<i32>>);
+
+#[derive(FromInput)]
+#[input(sep = "|")]
+struct Rule {
+    before: i32,
+    after: i32
+}
+
+#[derive(FromInput)]
+struct Update {
+    #[input(sep = ",")]
+    pages: Vec<i32>
+}
+
+#[derive(FromInput)]
+#[input(sections)]
+struct Manual {
+    rules: Vec<Rule>,
+    updates: Vec<Update>
+}
+
+pub fn parse(input: &str) -> ParseResult<PrintQueue> {
+    let manual = Manual::from_input(1, input)?;
+    let mut rules: Rules = DiGraph::new();
+    for rule in manual.rules {
+        rules.add_edge(rule.before, rule.after);
+    }
+    let updates: Vec<Vec<i32>> = manual.updates.into_iter().map(|update| update.pages).collect();
+    // The rules as a whole have cycles, but those between the pages of an update must not.
+    let update_lines = &parse::sections(input)[1].lines;
+    for (line, update) in update_lines.iter().zip(&updates) {
+        // Sorting an update goes through a graph of its pages, which would drop repeated ones.
+        let mut seen: HashSet<i32> = HashSet::new();
+        if let Some(page) = update.iter().find(|&&page| !seen.insert(page)) {
+            return Err(line.error(format!("page {} appears twice in the update", page)));
+        }
+        if let Some(cycle) = rules.induced_subgraph(update).find_cycle() {
+            return Err(line.error(format!("the pages of this update cannot be ordered: {}", cycle)));
+        }
+    }
+    Ok((rules, updates))
+}
+
+/// Pages read from `text` split on `sep`, with a problem for each piece that is not a page number.
+fn check_pages(line: usize, text: &str, sep: &str, problems: &mut Vec<Problem>) -> Vec<(usize, i32)> {
+    split_with_columns(text, sep).into_iter()
+        .filter_map(|(column, page)| match check_value::<i32>(line, column, page, "a page number") {
+            Ok(page) => Some((column, page)),
+            Err(problem) => {
+                problems.push(problem);
+                None
+            }
+        })
+        .collect()
+}
+
+/*
+    Rules `before|after`, a blank line, then updates of comma-separated pages. Every update needs an
+    odd number of distinct pages to have a middle one, and the rules between its pages must not form
+    a cycle. A rule about a page that no update contains is most likely a typo.
+*/
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = vec![];
+    let sections = parse::sections(input);
+    if sections.len() != 2 {
+        let line = sections.get(2).map(|section| section.first_line()).unwrap_or(1);
+        problems.push(Problem::new(line, 0, format!(
+            "expected rules and updates separated by a blank line, found {} section(s)", sections.len()
+        )));
+    }
+
+    let mut rules: Rules = DiGraph::new();
+    let mut rule_pages: Vec<(usize, usize, i32)> = vec![];
+    for line in sections.first().map(|section| &section.lines[..]).unwrap_or(&[]) {
+        let pages = check_pages(line.number, line.text, "|", &mut problems);
+        if line.text.split('|').count() != 2 {
+            problems.push(Problem::new(line.number, 0, "expected a rule 'before|after'"));
+        } else if let [(_, before), (_, after)] = pages[..] {
+            rules.add_edge(before, after);
+        }
+        rule_pages.extend(pages.iter().map(|&(column, page)| (line.number, column, page)));
+    }
+
+    let mut updated_pages: HashSet<i32> = HashSet::new();
+    for line in sections.get(1).map(|section| &section.lines[..]).unwrap_or(&[]) {
+        let pages = check_pages(line.number, line.text, ",", &mut problems);
+        let mut seen: HashSet<i32> = HashSet::new();
+        for &(column, page) in &pages {
+            if !seen.insert(page) {
+                problems.push(Problem::new(line.number, column, format!("page {} appears twice in the update", page)));
+            }
+        }
+        if seen.len().is_multiple_of(2) {
+            problems.push(Problem::new(line.number, 0, format!("update has {} pages, so no middle one", seen.len())));
+        }
+        let update: Vec<i32> = pages.iter().map(|&(_, page)| page).collect();
+        if let Some(cycle) = rules.induced_subgraph(&update).find_cycle() {
+            problems.push(Problem::new(line.number, 0, format!("the pages of this update cannot be ordered: {}", cycle)));
+        }
+        updated_pages.extend(update);
+    }
+
+    if sections.len() >= 2 {
+        for (line, column, page) in rule_pages {
+            if !updated_pages.contains(&page) {
+                problems.push(Problem::new(line, column, format!("page {} appears in no update", page)));
+            }
+        }
+    }
+    problems.sort_by_key(|problem| (problem.line, problem.column));
+    problems
+}
+
+/// The rules then the updates in the puzzle's format.
+pub fn format((rules, updates): &PrintQueue) -> String {
+    let mut text: String = rules.edges()
+        .map(|(before, after)| format!("{}|{}\n", before, after))
+        .collect();
+    text += "\n";
+    for update in updates {
+        text += &update.iter().map(|page| page.to_string()).collect::<Vec<String>>().join(",");
+        text += "\n";
+    }
+    text
+}
+
+/// First page of `update` that is followed by a page that should come before it, along with that page.
+fn get_incorrect_before(update: &[i32], rules: &Rules) -> Option<(i32, i32)> {
+    for (page_number_index, page_number) in update.iter().enumerate() {
+        let remainder: &[i32] = &update[page_number_index + 1..];
+        if let Some(incorrect_before) = remainder.iter().find(|other| rules.contains_edge(other, page_number)) {
+            return Some((*page_number, *incorrect_before));
+        }
+    }
+    None
+}
+
+fn get_middle_element(update: &[i32]) -> i32 {
+    update[update.len() / 2]
+}
+
+/// Reorders `update` by sorting the rules restricted to its pages topologically.
+fn fix_unordered(update: &[i32], rules: &Rules) -> Vec<i32> {
+    rules.induced_subgraph(update)
+        .topological_sort_kahn()
+        .unwrap_or_else(|cycle| panic!("Update {:?} cannot be ordered: {}, parse should have rejected it", update, cycle))
+}
+
+pub fn part_1((rules, updates): &PrintQueue) -> i64 {
+    let middle_elements: Vec<i32> = updates.iter()
+        .filter(|update| get_incorrect_before(update, rules).is_none())
+        .map(|update| get_middle_element(update))
+        .collect();
+    middle_elements.iter().map(|&page| page as i64).sum()
+}
+
+/// Reorders `update` by swapping the first misplaced pair of pages until no rule is broken.
+fn fix_unordered_by_swaps(update: &[i32], rules: &Rules) -> Vec<i32> {
+    swaps_to_fix(update, rules).0
+}
+
+/// The update fixed by the swap loop, and the pairs of pages it swapped.
+fn swaps_to_fix(update: &[i32], rules: &Rules) -> (Vec<i32>, Vec<(i32, i32)>) {
+    let mut new_update: Vec<i32> = update.to_vec();
+    let mut swaps: Vec<(i32, i32)> = vec![];
+    while let Some((page_number, incorrect_before)) = get_incorrect_before(&new_update, rules) {
+        let page_number_index = new_update.iter().position(|x| *x == page_number).unwrap();
+        let before_index = new_update.iter().position(|x| *x == incorrect_before).unwrap();
+        new_update.swap(page_number_index, before_index);
+        swaps.push((page_number, incorrect_before));
+    }
+    (new_update, swaps)
+}
+
+fn sum_of_fixed_middles((rules, updates): &PrintQueue, fix: fn(&[i32], &Rules) -> Vec<i32>) -> i64 {
+    let fixed_unordered: Vec<Vec<i32>> = updates.iter()
+        .filter(|update| get_incorrect_before(update, rules).is_some())
+        .map(|update| fix(update, rules))
+        .collect();
+    let middle_elements: Vec<i32> = fixed_unordered.iter()
+        .map(|update| get_middle_element(update))
+        .collect();
+    middle_elements.iter().map(|&page| page as i64).sum()
+}
+
+pub fn part_2(queue: &PrintQueue) -> i64 {
+    sum_of_fixed_middles(queue, fix_unordered)
+}
+
+/// The correctly-ordered updates for part 1, the swaps fixing the others for part 2, with their middle pages.
+pub fn explain((rules, updates): &PrintQueue, part: u8) -> Vec<Step> {
+    let pages = |update: &[i32]| update.iter().map(|page| page.to_string()).collect::<Vec<String>>().join(",");
+    updates.iter()
+        .filter(|update| get_incorrect_before(update, rules).is_none() == (part == 1))
+        .map(|update| {
+            if part == 1 {
+                Step::new(format!("{}: middle page {}", pages(update), get_middle_element(update)), vec![])
+            } else {
+                let (fixed, swaps) = swaps_to_fix(update, rules);
+                let details = swaps.iter().map(|(page, before)| format!("swap {} and {}", page, before)).collect();
+                Step::new(format!("{} -> {}: middle page {}", pages(update), pages(&fixed), get_middle_element(&fixed)), details)
+            }
+        })
+        .collect()
+}
+
+/// `part_2` with the original swap loop instead of a topological sort.
+pub fn part_2_reference(queue: &PrintQueue) -> i64 {
+    sum_of_fixed_middles(queue, fix_unordered_by_swaps)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_contents;
+    use proptest::prelude::*;
+    use proptest::collection::vec;
+    use proptest::sample::subsequence;
+
+    #[test]
+    fn fix_unordered_example_updates() {
+        let (rules, _) = parse(&read_contents("data/day_5/test.txt")).unwrap();
+        assert_eq!(get_incorrect_before(&[75, 97, 47, 61, 53], &rules), Some((75, 97)));
+        assert_eq!(fix_unordered(&[75, 97, 47, 61, 53], &rules), vec![97, 75, 47, 61, 53]);
+        assert_eq!(fix_unordered(&[61, 13, 29], &rules), vec![61, 29, 13]);
+        assert_eq!(fix_unordered(&[97, 13, 75, 29, 47], &rules), vec![97, 75, 47, 29, 13]);
+        assert_eq!(get_incorrect_before(&[97, 75, 47, 29, 13], &rules), None);
+    }
+
+    #[test]
+    fn explain_lists_the_swaps() {
+        let queue = parse(&read_contents("data/day_5/test.txt")).unwrap();
+        assert_eq!(explain(&queue, 1)[0], Step::new("75,47,61,53,29: middle page 61", vec![]));
+        assert_eq!(explain(&queue, 2)[0], Step::new("75,97,47,61,53 -> 97,75,47,61,53: middle page 47", vec![
+            "swap 75 and 97".to_string()
+        ]));
+    }
+
+    #[test]
+    fn parse_rejects_repeated_pages() {
+        let error = parse("47|53\n\n47,53,47\n").unwrap_err();
+        assert_eq!((error.line, error.message.as_str()), (3, "page 47 appears twice in the update"));
+    }
+
+    #[test]
+    fn check_input_reports_every_problem() {
+        let input = "47|53\n97|13\n13|47\n12|99\n\n47,53,13\n53,x,53\n97,13\n";
+        let problems: Vec<String> = check_input(input).iter().map(|problem| problem.to_string()).collect();
+        assert_eq!(problems, vec![
+            "line 4, column 1: page 12 appears in no update",
+            "line 4, column 4: page 99 appears in no update",
+            "line 7, column 4: expected a page number, found 'x'",
+            "line 7, column 6: page 53 appears twice in the update",
+            "line 8: update has 2 pages, so no middle one"
+        ]);
+        assert!(check_input(&read_contents("data/day_5/test.txt")).is_empty());
+    }
+
+    #[test]
+    fn parse_rejects_updates_on_a_cycle() {
+        let error = parse("1|2\n2|3\n3|1\n\n1,2\n1,2,3\n").err().unwrap();
+        assert_eq!(error.line, 6);
+    }
+
+    /// Rules consistent with a hidden order of some pages, and an update made of some of them.
+    fn rules_and_update() -> impl Strategy<Value = (Rules, Vec<i32>)> {
+        subsequence((10..100).collect::<Vec<i32>>(), 2..20)
+            .prop_shuffle()
+            .prop_flat_map(|order| {
+                let n = order.len();
+                (Just(order.clone()), vec(any::<bool>(), n * (n - 1) / 2), subsequence(order, 1..=n).prop_shuffle())
+            })
+            .prop_map(|(order, kept_rules, update)| {
+                let mut rules: Rules = DiGraph::new();
+                let pairs = (0..order.len()).flat_map(|i| (i + 1..order.len()).map(move |j| (i, j)));
+                for ((i, j), kept) in pairs.zip(kept_rules) {
+                    if kept {
+                        rules.add_edge(order[i], order[j]);
+                    }
+                }
+                (rules, update)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn fix_unordered_returns_an_ordered_permutation((rules, update) in rules_and_update()) {
+            let fixed = fix_unordered(&update, &rules);
+            prop_assert_eq!(get_incorrect_before(&fixed, &rules), None);
+            let (mut sorted_fixed, mut sorted_update) = (fixed.clone(), update.clone());
+            sorted_fixed.sort();
+            sorted_update.sort();
+            prop_assert_eq!(sorted_fixed, sorted_update);
+        }
+    }
+}
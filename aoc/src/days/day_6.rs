@@ -0,0 +1,330 @@
+use std::fmt;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+
+use crate::diagnostics::{check_grid, Problem};
+use crate::explain::Step;
+use crate::parse::{ParseError, ParseResult};
+
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+enum Direction {
+    North,
+    South,
+    East,
+    West
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Direction::North => write!(f, "North"),
+            Direction::East => write!(f, "East"),
+            Direction::South => write!(f, "South"),
+            Direction::West => write!(f, "West")
+        }
+    }
+}
+#[derive(PartialEq, Eq, Hash, Clone, Copy)]
+struct Guard {
+    row: i32,
+    col: i32,
+    direction: Direction
+}
+
+impl fmt::Display for Guard {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{},{}) ", self.row, self.col, self.direction)
+    }
+}
+
+/// The lab: rocks, the guard's starting position and the size of the map.
+#[derive(Clone)]
+pub struct Game {
+    rocks: HashSet<(i32, i32)>,
+    guard: Guard,
+    n_rows: i32,
+    n_cols: i32
+}
+
+pub fn parse(input: &str) -> ParseResult<Game> {
+    let mut rocks: HashSet<(i32, i32)> = HashSet::new();
+    let mut row: i32 = 0;
+    let mut guard: Option<Guard> = None;
+    let mut n_cols: i32 = 0;
+    for line in input.lines() {
+        let mut column: i32 = 0;
+        for c in line.chars() {
+            if c == '#' {
+                rocks.insert((row, column));
+            } else if c == '^' {
+                if guard.is_some() {
+                    return Err(ParseError::new(row as usize + 1, "another guard, the map must have only one"));
+                }
+                guard = Some(Guard { row, col:column, direction: Direction::North });
+            }
+            column += 1;
+        }
+        if row > 0 && column != n_cols {
+            return Err(ParseError::new(row as usize + 1, format!("row has {} cells while the first one has {}", column, n_cols)));
+        }
+        n_cols = column;
+        row += 1;
+    }
+    let guard = guard.ok_or_else(|| ParseError::new(1, "no guard '^' on the map"))?;
+    let game = Game {rocks, guard, n_rows: row, n_cols };
+    // Both parts assume that the guard eventually leaves the map.
+    if walks_in_circles(&game.rocks, &game) {
+        return Err(ParseError::new(guard.row as usize + 1, "the guard walks in circles and never leaves the map"));
+    }
+    Ok(game)
+}
+
+/// A rectangular map of `.` and `#` with exactly one guard `^`, who eventually leaves it.
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let (rows, mut problems) = check_grid(input, |c| ".#^".contains(c), "'.', '#' or '^'");
+    let guards: Vec<(usize, usize)> = rows.iter().enumerate()
+        .flat_map(|(row, cells)| cells.iter().enumerate().filter(|&(_, &c)| c == '^').map(move |(col, _)| (row, col)))
+        .collect();
+    match guards[..] {
+        [] => problems.push(Problem::new(1, 0, "no guard '^' on the map")),
+        [_] => {}
+        [_, ref others @ ..] => problems.extend(others.iter()
+            .map(|&(row, col)| Problem::new(row + 1, col + 1, "another guard, the map must have only one")))
+    }
+    if problems.is_empty() {
+        if let Err(e) = parse(input) {
+            problems.push(Problem::new(e.line, 0, e.message));
+        }
+    }
+    problems
+}
+
+fn next_straight_position(guard: Guard) -> (i32, i32) {
+    match guard.direction {
+        Direction::North => {
+            (guard.row - 1, guard.col)
+        }
+        Direction::East => {
+            (guard.row, guard.col + 1)
+        }
+        Direction::South => {
+            (guard.row + 1, guard.col)
+        }
+        Direction::West => {
+            (guard.row, guard.col - 1)
+        }
+    }
+}
+
+fn next_direction(direction: Direction) -> Direction {
+    match direction {
+        Direction::North => {
+            Direction::East
+        }
+        Direction::East => {
+            Direction::South
+        }
+        Direction::South => {
+            Direction::West
+        }
+        Direction::West => {
+            Direction::North
+        }
+    }
+}
+
+/// The guard's next step. A guard boxed in by four rocks stays where it is, which counts as a loop.
+fn next_position(rocks: &HashSet<(i32, i32)>, mut guard: Guard) -> Guard {
+    for _ in 0..4 {
+        let next_position: (i32, i32) = next_straight_position(guard);
+        if !rocks.contains(&next_position) {
+            return Guard { row: next_position.0, col: next_position.1, direction: guard.direction };
+        }
+        guard.direction = next_direction(guard.direction);
+    }
+    guard
+}
+
+fn contains_loop(game: Game) -> bool {
+    let mut guard: Guard = game.guard;
+    let mut n_visited_positions: usize = 0;
+    // Past this many steps some (position, direction) must have repeated.
+    let max_steps = 4 * game.n_rows as usize * game.n_cols as usize;
+
+    while guard.row < game.n_rows && guard.row >= 0 && guard.col < game.n_cols && guard.col >= 0 {
+        if n_visited_positions > max_steps {
+            return true;
+        }
+        guard = next_position(&game.rocks, guard);
+        n_visited_positions += 1;
+    }
+    false
+}
+
+/// Whether the guard walks in circles, detected by coming back to a position facing the same way.
+fn walks_in_circles(rocks: &HashSet<(i32, i32)>, game: &Game) -> bool {
+    find_loop(rocks, game).is_some()
+}
+
+/// The first state the guard comes back to, with the step it was first reached at and the length of the loop.
+fn find_loop(rocks: &HashSet<(i32, i32)>, game: &Game) -> Option<(Guard, usize, usize)> {
+    let mut seen: HashMap<Guard, usize> = HashMap::new();
+    let mut guard: Guard = game.guard;
+    let mut step = 0;
+
+    while guard.row < game.n_rows && guard.row >= 0 && guard.col < game.n_cols && guard.col >= 0 {
+        if let Some(&first_step) = seen.get(&guard) {
+            return Some((guard, first_step, step - first_step));
+        }
+        seen.insert(guard, step);
+        guard = next_position(rocks, guard);
+        step += 1;
+    }
+    None
+}
+
+fn visited_positions(game: &Game) -> HashSet<(i32, i32)> {
+    let mut visited_positions: HashSet<(i32, i32)> = HashSet::new();
+    let mut guard: Guard = game.guard;
+
+    while guard.row < game.n_rows && guard.row >= 0 && guard.col < game.n_cols && guard.col >= 0 {
+        let pos: (i32, i32) = (guard.row, guard.col);
+        visited_positions.insert(pos);
+        guard = next_position(&game.rocks, guard);
+    }
+    visited_positions
+}
+
+pub fn part_1(game: &Game) -> i64 {
+    visited_positions(game).len() as i64
+}
+
+/// The map with the guard's path marked `X`, like the puzzle statement.
+pub fn render_part_1(game: &Game, out: &mut impl Write) -> io::Result<()> {
+    let visited = visited_positions(game);
+    for row in 0..game.n_rows {
+        let line: String = (0..game.n_cols)
+            .map(|col| if visited.contains(&(row, col)) { 'X' } else if game.rocks.contains(&(row, col)) { '#' } else { '.' })
+            .collect();
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+/*
+    A new rock can only change the guard's walk if it lies on the original path, so those are the
+    only cells worth trying, and loops are detected exactly instead of by counting steps.
+*/
+pub fn part_2(game: &Game) -> i64 {
+    let mut candidates = visited_positions(game);
+    candidates.remove(&(game.guard.row, game.guard.col));
+    let mut rocks = game.rocks.clone();
+    candidates.into_iter()
+        .filter(|&cell| {
+            rocks.insert(cell);
+            let loops = walks_in_circles(&rocks, game);
+            rocks.remove(&cell);
+            loops
+        })
+        .count() as i64
+}
+
+/// How the guard leaves the map for part 1, and the loop each new obstacle causes for part 2.
+pub fn explain(game: &Game, part: u8) -> Vec<Step> {
+    if part == 1 {
+        let mut guard: Guard = game.guard;
+        let mut n_steps = 0;
+        loop {
+            let next = next_position(&game.rocks, guard);
+            if !(next.row < game.n_rows && next.row >= 0 && next.col < game.n_cols && next.col >= 0) {
+                break;
+            }
+            guard = next;
+            n_steps += 1;
+        }
+        return vec![Step::new(format!("the guard visits {} positions", visited_positions(game).len()), vec![
+            format!("starting at ({},{}) facing {}", game.guard.row, game.guard.col, game.guard.direction),
+            format!("leaving the map from ({},{}) facing {} after {} steps", guard.row, guard.col, guard.direction, n_steps)
+        ])];
+    }
+    let mut candidates: Vec<(i32, i32)> = visited_positions(game).into_iter().collect();
+    candidates.retain(|&cell| cell != (game.guard.row, game.guard.col));
+    candidates.sort();
+    let mut rocks = game.rocks.clone();
+    candidates.into_iter()
+        .filter_map(|cell| {
+            rocks.insert(cell);
+            let found = find_loop(&rocks, game);
+            rocks.remove(&cell);
+            found.map(|(guard, first_step, length)| Step::new(format!("obstacle at ({},{})", cell.0, cell.1), vec![
+                format!("the guard reaches ({},{}) facing {} after {} steps and again every {} steps",
+                        guard.row, guard.col, guard.direction, first_step, length)
+            ]))
+        })
+        .collect()
+}
+
+/// `part_2` trying a rock on every free cell.
+pub fn part_2_reference(game: &Game) -> i64 {
+    let mut n_loops = 0;
+    for i in 0..game.n_rows {
+        for j in 0..game.n_cols {
+            if !game.rocks.contains(&(i, j)) && (i != game.guard.row || j != game.guard.col) {
+                let mut modified_rocks = game.rocks.clone();
+                modified_rocks.insert((i,j));
+                if contains_loop(Game {rocks: modified_rocks, guard: game.guard, n_rows: game.n_rows, n_cols: game.n_cols}) {
+                    n_loops += 1;
+                }
+            }
+        }
+    }
+    n_loops
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::read_contents;
+
+    fn example() -> Game {
+        parse(&read_contents("data/day_6/test.txt")).unwrap()
+    }
+
+    #[test]
+    fn contains_loop_with_extra_rock() {
+        let game = example();
+        let mut rocks = game.rocks.clone();
+        rocks.insert((6, 3));
+        assert!(contains_loop(Game { rocks, ..game }));
+
+        let game = example();
+        assert!(!contains_loop(game));
+    }
+
+    #[test]
+    fn explain_finds_the_puzzle_obstacles() {
+        let obstacles: Vec<String> = explain(&example(), 2).into_iter().map(|step| step.summary).collect();
+        assert_eq!(obstacles, ["obstacle at (6,3)", "obstacle at (7,6)", "obstacle at (7,7)",
+                               "obstacle at (8,1)", "obstacle at (8,3)", "obstacle at (9,7)"]);
+        assert_eq!(explain(&example(), 1)[0].details[1], "leaving the map from (9,7) facing South after 44 steps");
+    }
+
+    #[test]
+    fn check_input_finds_guard_problems() {
+        assert_eq!(check_input("..#.\n.^..\n..^."), vec![Problem::new(3, 3, "another guard, the map must have only one")]);
+        assert_eq!(check_input("...\n..."), vec![Problem::new(1, 0, "no guard '^' on the map")]);
+        assert_eq!(check_input(".#.\n#^#\n.#."), vec![Problem::new(2, 0, "the guard walks in circles and never leaves the map")]);
+    }
+
+    #[test]
+    fn parse_rejects_a_guard_that_never_leaves() {
+        let error = parse(".#.\n#^#\n.#.").err().unwrap();
+        assert_eq!(error.line, 2);
+        assert!(parse("...\n.^\n...").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_a_second_guard() {
+        assert_eq!(parse("..#.\n.^..\n..^.").err(), Some(ParseError::new(3, "another guard, the map must have only one")));
+    }
+}
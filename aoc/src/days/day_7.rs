@@ -0,0 +1,290 @@
+use std::fmt;
+use std::hash::Hash;
+use itertools::Itertools;
+
+use crate::combinatorics::cartesian_power;
+use crate::diagnostics::{check_value, split_with_columns, Problem};
+use crate::explain::Step;
+use crate::parse::{self, ParseError, ParseResult};
+use crate::FromInput;
+
+#[derive(FromInput, Debug, PartialEq, Eq)]
+#[input(sep = ": ")]
+pub struct Equation {
+    result: i64,
+    #[input(sep = " ", non_empty)]
+    terms: Vec<i64>
+}
+
+impl fmt::Display for Equation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({}= {}) ", self.result, self.terms.iter().join(","))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash)]
+enum Operand {
+    Add,
+    Mult,
+    Concat
+}
+
+impl fmt::Display for Operand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Operand::Add => write!(f, "+"),
+            Operand::Mult => write!(f, "*"),
+            Operand::Concat => write!(f, "||")
+        }
+    }
+}
+
+pub fn parse(input: &str) -> ParseResult<Vec<Equation>> {
+    let equations: Vec<Equation> = parse::parse_lines(input)?;
+    let mut total: i64 = 0;
+    for (index, eq) in equations.iter().enumerate() {
+        // Solving relies on non-negative numbers, and the answers must fit in an i64.
+        if eq.result < 0 || eq.terms.iter().any(|&term| term < 0) {
+            return Err(ParseError::new(index + 1, "numbers must not be negative"));
+        }
+        total = total.checked_add(eq.result)
+            .ok_or_else(|| ParseError::new(index + 1, "the results add up to more than an i64 can hold"))?;
+    }
+    Ok(equations)
+}
+
+/// Every line is `result: terms` with space-separated terms, all numbers being non-negative, and the
+/// results must add up to something that fits in an i64.
+pub fn check_input(input: &str) -> Vec<Problem> {
+    let mut problems: Vec<Problem> = vec![];
+    let mut total: Option<i64> = Some(0);
+    for line in parse::lines(input) {
+        let Some((result, terms)) = line.text.split_once(": ") else {
+            problems.push(Problem::new(line.number, 0, "expected 'result: terms'"));
+            continue;
+        };
+        let terms_column = result.chars().count() + 3;
+        let numbers = std::iter::once((1, result))
+            .chain(split_with_columns(terms, " ").into_iter().map(|(column, term)| (terms_column + column - 1, term)));
+        for (column, number) in numbers {
+            match check_value::<i64>(line.number, column, number, "a number") {
+                Ok(value) if value < 0 => problems.push(Problem::new(line.number, column, "numbers must not be negative")),
+                Ok(_) => {}
+                Err(problem) => problems.push(problem)
+            }
+        }
+        if let Ok(result) = result.parse::<i64>() {
+            if total.is_some() {
+                total = total.and_then(|total| total.checked_add(result));
+                if total.is_none() {
+                    problems.push(Problem::new(line.number, 1, "the results add up to more than an i64 can hold"));
+                }
+            }
+        }
+    }
+    problems
+}
+
+/// The equations in the puzzle's format, one per line.
+pub fn format(equations: &[Equation]) -> String {
+    equations.iter()
+        .map(|eq| format!("{}: {}\n", eq.result, eq.terms.iter().join(" ")))
+        .collect()
+}
+
+fn compute(a: &i64, b: &i64, op:&Operand) -> i64 {
+    match op {
+        Operand::Add => {
+            a + b
+        }
+        Operand::Mult => {
+            a * b
+        }
+        Operand::Concat => {
+            (a.to_string() + &b.to_string()).parse().unwrap()
+        }
+    }
+}
+
+fn check_equation_with_operands(eq: &Equation, operands: &[Operand]) -> bool {
+    let first_term = eq.terms[0];
+    let rest_of_terms = &eq.terms[1..eq.terms.len()];
+    let mut result = first_term;
+    for (term, op) in rest_of_terms.iter().zip(operands.iter()) {
+        result = compute(&result, term, op);
+    }
+    result == eq.result
+}
+
+fn can_be_filled_with_operands(eq: &Equation, distinct_operands: &[Operand]) -> Option<Vec<Operand>> {
+    let n_operands = eq.terms.len() - 1;
+    cartesian_power(distinct_operands, n_operands)
+        .find(|operands| check_equation_with_operands(eq, operands))
+}
+
+/*
+    Works backwards from the result: the last operation must be undoable from `target` (a
+    subtraction that stays non-negative, an exact division, or stripping the term's digits), which
+    cuts most branches right away. This relies on the terms being non-negative, as in the puzzle.
+    On success, `chosen` ends with the operands used, from left to right.
+*/
+fn find_operands(target: i64, terms: &[i64], distinct_operands: &[Operand], chosen: &mut Vec<Operand>) -> bool {
+    let Some((&last, rest)) = terms.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return target == last;
+    }
+    for &op in distinct_operands {
+        let found = match op {
+            Operand::Add => target >= last && find_operands(target - last, rest, distinct_operands, chosen),
+            // Anything times zero is zero, so the operands before do not matter.
+            Operand::Mult if last == 0 => {
+                if target == 0 {
+                    chosen.extend(std::iter::repeat_n(Operand::Add, rest.len() - 1));
+                }
+                target == 0
+            }
+            Operand::Mult => target % last == 0 && find_operands(target / last, rest, distinct_operands, chosen),
+            Operand::Concat => match 10_i64.checked_pow(last.to_string().len() as u32) {
+                Some(shift) => target % shift == last && find_operands(target / shift, rest, distinct_operands, chosen),
+                None => false
+            }
+        };
+        if found {
+            chosen.push(op);
+            return true;
+        }
+    }
+    false
+}
+
+fn can_reach(target: i64, terms: &[i64], distinct_operands: &[Operand]) -> bool {
+    find_operands(target, terms, distinct_operands, &mut vec![])
+}
+
+fn sum_of_solvable(equations: &[Equation], is_solvable: impl Fn(&Equation) -> bool) -> i64 {
+    equations.iter()
+        .filter(|eq| is_solvable(eq))
+        .map(|eq| eq.result)
+        .sum()
+}
+
+const PART_1_OPERANDS: [Operand; 2] = [Operand::Add, Operand::Mult];
+const PART_2_OPERANDS: [Operand; 3] = [Operand::Add, Operand::Mult, Operand::Concat];
+
+pub fn part_1(equations: &[Equation]) -> i64 {
+    sum_of_solvable(equations, |eq| can_reach(eq.result, &eq.terms, &PART_1_OPERANDS))
+}
+
+pub fn part_2(equations: &[Equation]) -> i64 {
+    sum_of_solvable(equations, |eq| can_reach(eq.result, &eq.terms, &PART_2_OPERANDS))
+}
+
+fn format_op_and_eq(eq: &Equation, operands: &[Operand]) -> String {
+    let mut result: String = format!("{} = {}", eq.result, eq.terms[0]);
+    for (term, op) in eq.terms[1..].iter().zip(operands.iter()) {
+        result += &format!(" {} {}", op, term);
+    }
+    result
+}
+
+/// Each solvable equation with its operands, and the intermediate results from left to right.
+pub fn explain(equations: &[Equation], part: u8) -> Vec<Step> {
+    let distinct_operands: &[Operand] = if part == 1 { &PART_1_OPERANDS } else { &PART_2_OPERANDS };
+    equations.iter()
+        .filter_map(|eq| {
+            let mut operands: Vec<Operand> = vec![];
+            if !find_operands(eq.result, &eq.terms, distinct_operands, &mut operands) {
+                return None;
+            }
+            let mut value = eq.terms[0];
+            let details: Vec<String> = eq.terms[1..].iter().zip(&operands)
+                .map(|(term, op)| {
+                    let next_value = compute(&value, term, op);
+                    let detail = format!("{} {} {} = {}", value, op, term, next_value);
+                    value = next_value;
+                    detail
+                })
+                .collect();
+            Some(Step::new(format_op_and_eq(eq, &operands), details))
+        })
+        .collect()
+}
+
+/// `part_1` trying every combination of operands.
+pub fn part_1_reference(equations: &[Equation]) -> i64 {
+    sum_of_solvable(equations, |eq| can_be_filled_with_operands(eq, &PART_1_OPERANDS).is_some())
+}
+
+/// `part_2` trying every combination of operands.
+pub fn part_2_reference(equations: &[Equation]) -> i64 {
+    sum_of_solvable(equations, |eq| can_be_filled_with_operands(eq, &PART_2_OPERANDS).is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compute_applies_each_operand() {
+        assert_eq!(compute(&15, &6, &Operand::Add), 21);
+        assert_eq!(compute(&15, &6, &Operand::Mult), 90);
+        assert_eq!(compute(&15, &6, &Operand::Concat), 156);
+        assert_eq!(compute(&12, &345, &Operand::Concat), 12345);
+    }
+
+    #[test]
+    fn check_equation_needs_concat_for_some() {
+        let eq = Equation { result: 7290, terms: vec![6, 8, 6, 15] };
+        assert!(can_be_filled_with_operands(&eq, &PART_1_OPERANDS).is_none());
+        assert!(can_be_filled_with_operands(&eq, &PART_2_OPERANDS).is_some());
+        assert!(!can_reach(eq.result, &eq.terms, &PART_1_OPERANDS));
+        assert!(can_reach(eq.result, &eq.terms, &PART_2_OPERANDS));
+    }
+
+    #[test]
+    fn parse_rejects_negative_numbers_and_overflowing_results() {
+        assert_eq!(parse("10: 5 2\n-3: 1 2\n").err().unwrap().line, 2);
+        assert_eq!(parse("10: 5 -2\n").err().unwrap().line, 1);
+        let big = format!("{}: 1\n{}: 1\n", i64::MAX, 1);
+        assert_eq!(parse(&big).err().unwrap().line, 2);
+    }
+
+    #[test]
+    fn parse_rejects_equations_without_terms() {
+        assert_eq!(parse("190: \n").unwrap_err(), ParseError::new(1, "expected at least one value for terms"));
+        assert_eq!(parse("3: 1 2\n190:\n").unwrap_err(), ParseError::new(2, "invalid value '190:'"));
+    }
+
+    #[test]
+    fn check_input_locates_bad_numbers() {
+        let problems: Vec<String> = check_input("190: 10 19\n-5: 1 2\n3 4\n7: 1 x\n").iter().map(|p| p.to_string()).collect();
+        assert_eq!(problems, vec![
+            "line 2, column 1: numbers must not be negative",
+            "line 3: expected 'result: terms'",
+            "line 4, column 6: expected a number, found 'x'"
+        ]);
+    }
+
+    #[test]
+    fn explain_shows_the_operands() {
+        let equations = parse(&crate::utils::read_contents("data/day_7/test.txt")).unwrap();
+        let steps = explain(&equations, 2);
+        assert_eq!(steps.len(), 6);
+        assert_eq!(steps[3], Step::new("7290 = 6 * 8 || 6 * 15", vec![
+            "6 * 8 = 48".to_string(), "48 || 6 = 486".to_string(), "486 * 15 = 7290".to_string()
+        ]));
+        let mut operands = vec![];
+        assert!(find_operands(0, &[5, 3, 0], &PART_1_OPERANDS, &mut operands));
+        assert_eq!(operands, vec![Operand::Add, Operand::Mult]);
+    }
+
+    #[test]
+    fn can_reach_handles_zero_terms() {
+        assert!(can_reach(0, &[5, 0], &[Operand::Mult]));
+        assert!(can_reach(50, &[5, 0], &[Operand::Concat]));
+        assert!(!can_reach(5, &[5, 0], &[Operand::Mult, Operand::Concat]));
+        assert!(can_reach(5, &[5, 0], &PART_1_OPERANDS));
+    }
+}
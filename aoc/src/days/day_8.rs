@@ -0,0 +1,224 @@
+use std::{collections::HashSet, fmt};
+use std::hash::Hash;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{self, Write};
+
+use crate::combinatorics::combinations;
+use crate::diagnostics::{check_grid, Problem};
+use crate::explain::Step;
+use crate::parse::ParseResult;
+
+#[derive(PartialEq, Eq, Clone, Copy, Hash, Debug)]
+pub struct Pos {
+    row: i32,
+    col: i32
+}
+
+impl fmt::Display for Pos {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "({},{})", self.row, self.col)
+    }
+}
+
+pub struct Game {
+    n_rows: i32,
+    n_cols: i32
+}
+
+pub type Antennas=HashMap<char, Vec<Pos>>;
+
+/// Size of the map and the positions of the antennas of each frequency.
+pub type City = (Game, Antennas);
+
+pub fn parse(input: &str) -> ParseResult<City> {
+    let mut all_antennas: Antennas = HashMap::new();
+    let mut row: i32 = 0;
+    let mut col: i32 = 0;
+    for line in input.lines() {
+        col = 0;
+        for c in line.chars() {
+            if c != '.' {
+                let pos = Pos { row, col };
+                all_antennas.entry(c).or_default().push(pos);
+            }
+            col += 1;
+        }
+        row += 1;
+    }
+    Ok((Game {n_rows: row, n_cols: col}, all_antennas))
+}
+
+/// A rectangular map of `.` and antennas, whose frequencies are letters or digits.
+pub fn check_input(input: &str) -> Vec<Problem> {
+    check_grid(input, |c| c == '.' || c.is_ascii_alphanumeric(), "'.' or an antenna (a letter or digit)").1
+}
+
+fn is_antinode_valid(pos: &Pos, n_rows: i32, n_cols: i32) -> bool {
+    pos.row >= 0 && pos.row < n_rows && pos.col >= 0 && pos.col < n_cols
+}
+
+fn get_antinodes(positions: &[Pos], game: &Game) -> HashSet<Pos> {
+    let mut antinodes: HashSet<Pos> = HashSet::new();
+    for pair in combinations(positions, 2) {
+        let (pos_a, pos_b) = (pair[0], pair[1]);
+        let row_diff = pos_b.row - pos_a.row;
+        let col_diff = pos_b.col - pos_a.col;
+        /*  One antinode on each side, as far from the nearest antenna as the antennas are apart
+            #..
+            .a.
+            ..a
+            ...#
+        */
+        antinodes.insert(Pos { row: pos_a.row - row_diff, col: pos_a.col - col_diff });
+        antinodes.insert(Pos { row: pos_b.row + row_diff, col: pos_b.col + col_diff });
+    }
+    antinodes.iter()
+        .filter(|pos| is_antinode_valid(pos, game.n_rows, game.n_cols))
+        .copied()
+        .collect()
+}
+
+/// Antinodes with resonant harmonics: every position in line with two antennas, at any multiple of their distance.
+fn get_resonant_antinodes(positions: &[Pos], game: &Game) -> HashSet<Pos> {
+    let mut antinodes: HashSet<Pos> = HashSet::new();
+    for pair in combinations(positions, 2) {
+        let (pos_a, pos_b) = (pair[0], pair[1]);
+        let row_diff = pos_b.row - pos_a.row;
+        let col_diff = pos_b.col - pos_a.col;
+        for direction in [-1, 1] {
+            let mut pos = pos_a;
+            while is_antinode_valid(&pos, game.n_rows, game.n_cols) {
+                antinodes.insert(pos);
+                pos = Pos { row: pos.row + direction * row_diff, col: pos.col + direction * col_diff };
+            }
+        }
+    }
+    antinodes
+}
+
+fn all_antinodes((game, all_antennas): &City, get_antinodes: fn(&[Pos], &Game) -> HashSet<Pos>) -> HashSet<Pos> {
+    all_antennas.values()
+        .flat_map(|antennas| get_antinodes(antennas, game))
+        .collect()
+}
+
+/*
+    Draws the map like the puzzle statement, antinodes being `#`. Its part 1 picture shows antinodes
+    over the antennas they share a cell with, and its part 2 picture the other way round.
+*/
+fn write_antinodes((game, all_antennas): &City, antinodes: &HashSet<Pos>, antennas_on_top: bool, out: &mut impl Write) -> io::Result<()> {
+    let antennas: HashMap<Pos, char> = all_antennas.iter()
+        .flat_map(|(&frequency, positions)| positions.iter().map(move |&pos| (pos, frequency)))
+        .collect();
+    for row in 0..game.n_rows {
+        let line: String = (0..game.n_cols)
+            .map(|col| Pos { row, col })
+            .map(|pos| match (antinodes.contains(&pos), antennas.get(&pos)) {
+                (true, Some(&frequency)) if antennas_on_top => frequency,
+                (true, _) => '#',
+                (false, antenna) => antenna.copied().unwrap_or('.')
+            })
+            .collect();
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+pub fn part_1(city: &City) -> i64 {
+    all_antinodes(city, get_antinodes).len() as i64
+}
+
+pub fn part_2(city: &City) -> i64 {
+    all_antinodes(city, get_resonant_antinodes).len() as i64
+}
+
+/// Each antinode and the pairs of antennas creating it.
+pub fn explain((game, all_antennas): &City, part: u8) -> Vec<Step> {
+    let get_antinodes: fn(&[Pos], &Game) -> HashSet<Pos> = if part == 1 { get_antinodes } else { get_resonant_antinodes };
+    let mut frequencies: Vec<&char> = all_antennas.keys().collect();
+    frequencies.sort();
+    let mut pairs_by_antinode: BTreeMap<(i32, i32), Vec<String>> = BTreeMap::new();
+    for frequency in frequencies {
+        for pair in combinations(&all_antennas[frequency], 2) {
+            for antinode in get_antinodes(&pair, game) {
+                pairs_by_antinode.entry((antinode.row, antinode.col)).or_default()
+                    .push(format!("antennas '{}' at {} and {}", frequency, pair[0], pair[1]));
+            }
+        }
+    }
+    pairs_by_antinode.into_iter()
+        .map(|((row, col), pairs)| Step::new(format!("antinode at {}", Pos { row, col }), pairs))
+        .collect()
+}
+
+/// The map with the antinodes of part 1.
+pub fn render_part_1(city: &City, out: &mut impl Write) -> io::Result<()> {
+    write_antinodes(city, &all_antinodes(city, get_antinodes), false, out)
+}
+
+/// The map with the resonant antinodes of part 2.
+pub fn render_part_2(city: &City, out: &mut impl Write) -> io::Result<()> {
+    write_antinodes(city, &all_antinodes(city, get_resonant_antinodes), true, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn get_antinodes_for_two_antennas() {
+        let game = Game { n_rows: 10, n_cols: 10 };
+        let antinodes = get_antinodes(&[Pos { row: 3, col: 4 }, Pos { row: 5, col: 5 }], &game);
+        let expected: HashSet<Pos> = HashSet::from([Pos { row: 1, col: 3 }, Pos { row: 7, col: 6 }]);
+        assert_eq!(antinodes, expected);
+    }
+
+    #[test]
+    fn get_antinodes_on_same_row_and_out_of_bounds() {
+        let game = Game { n_rows: 10, n_cols: 10 };
+        let antinodes = get_antinodes(&[Pos { row: 2, col: 1 }, Pos { row: 2, col: 4 }], &game);
+        assert_eq!(antinodes, HashSet::from([Pos { row: 2, col: 7 }]));
+    }
+
+    #[test]
+    fn explain_names_the_antennas_behind_each_antinode() {
+        let city = parse(&crate::utils::read_contents("data/day_8/test.txt")).unwrap();
+        let steps = explain(&city, 1);
+        assert_eq!(steps.len(), 14);
+        assert_eq!(steps[0], Step::new("antinode at (0,6)", vec!["antennas '0' at (2,5) and (4,4)".to_string()]));
+        assert_eq!(explain(&city, 2).len(), 34);
+    }
+
+    #[test]
+    fn get_resonant_antinodes_includes_antennas() {
+        let game = Game { n_rows: 10, n_cols: 10 };
+        let antinodes = get_resonant_antinodes(&[Pos { row: 0, col: 0 }, Pos { row: 1, col: 3 }], &game);
+        let expected: HashSet<Pos> = HashSet::from([
+            Pos { row: 0, col: 0 }, Pos { row: 1, col: 3 }, Pos { row: 2, col: 6 }, Pos { row: 3, col: 9 }
+        ]);
+        assert_eq!(antinodes, expected);
+    }
+
+    /// A map size and distinct antenna positions on it.
+    fn game_and_antennas() -> impl Strategy<Value = (i32, i32, Vec<Pos>)> {
+        (1..30i32, 1..30i32).prop_flat_map(|(n_rows, n_cols)| {
+            let positions = prop::collection::hash_set((0..n_rows, 0..n_cols), 0..8)
+                .prop_map(|cells| cells.into_iter().map(|(row, col)| Pos { row, col }).collect::<Vec<Pos>>());
+            (Just(n_rows), Just(n_cols), positions)
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn antinodes_lie_inside_the_map((n_rows, n_cols, antennas) in game_and_antennas()) {
+            let game = Game { n_rows, n_cols };
+            let antinodes = get_antinodes(&antennas, &game);
+            let resonant_antinodes = get_resonant_antinodes(&antennas, &game);
+            for pos in antinodes.iter().chain(resonant_antinodes.iter()) {
+                prop_assert!(is_antinode_valid(pos, n_rows, n_cols), "{} is outside the map", pos);
+            }
+            prop_assert!(antinodes.is_subset(&resonant_antinodes) || antennas.len() < 2);
+        }
+    }
+}
@@ -0,0 +1,59 @@
+pub mod day_1;
+pub mod day_2;
+pub mod day_3;
+pub mod day_4;
+pub mod day_5;
+pub mod day_6;
+pub mod day_7;
+pub mod day_8;
+
+use crate::diagnostics::Problem;
+use crate::explain::Step;
+use crate::parse::ParseResult;
+
+/// Explains the answer of a part from the raw input, see `explain`.
+pub type Explainer = fn(&str, u8) -> ParseResult<Vec<Step>>;
+
+/// Entry points of a day working on the raw input, for the runner, tests and benchmarks.
+pub struct Solution {
+    pub day: u8,
+    pub part_1: fn(&str) -> ParseResult<i64>,
+    pub part_2: fn(&str) -> ParseResult<i64>,
+    /// Every problem with the input, see `diagnostics`.
+    pub check_input: fn(&str) -> Vec<Problem>,
+    /// Steps behind the answer of a part, for the days that can explain it.
+    pub explain: Option<Explainer>
+}
+
+macro_rules! solution {
+    ($day:literal, $module:ident) => {
+        solution!(@ $day, $module, None)
+    };
+    ($day:literal, $module:ident, explained) => {
+        solution!(@ $day, $module, Some(|input, part| Ok($module::explain(&$module::parse(input)?, part))))
+    };
+    (@ $day:literal, $module:ident, $explain:expr) => {
+        Solution {
+            day: $day,
+            part_1: |input| Ok($module::part_1(&$module::parse(input)?)),
+            part_2: |input| Ok($module::part_2(&$module::parse(input)?)),
+            check_input: $module::check_input,
+            explain: $explain
+        }
+    };
+}
+
+pub const SOLUTIONS: [Solution; 8] = [
+    solution!(1, day_1),
+    solution!(2, day_2, explained),
+    solution!(3, day_3),
+    solution!(4, day_4),
+    solution!(5, day_5, explained),
+    solution!(6, day_6, explained),
+    solution!(7, day_7, explained),
+    solution!(8, day_8, explained)
+];
+
+pub fn solution(day: u8) -> Option<&'static Solution> {
+    SOLUTIONS.iter().find(|solution| solution.day == day)
+}
@@ -0,0 +1,128 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::parse;
+
+/*
+    Input diagnostics: each day declares the invariants its input must satisfy in a `check_input`
+    function, which reports every problem it finds instead of stopping at the first one like the
+    parsers do. Lines and columns are 1-based, and columns count characters.
+*/
+
+/// Something wrong with an input. A column of 0 means the whole line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Problem {
+    pub line: usize,
+    pub column: usize,
+    pub message: String
+}
+
+impl Problem {
+    pub fn new(line: usize, column: usize, message: impl Into<String>) -> Problem {
+        Problem { line, column, message: message.into() }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.column == 0 {
+            write!(f, "line {}: {}", self.line, self.message)
+        } else {
+            write!(f, "line {}, column {}: {}", self.line, self.column, self.message)
+        }
+    }
+}
+
+/// The pieces of `text` separated by `sep`, each with the column it starts at.
+pub fn split_with_columns<'a>(text: &'a str, sep: &str) -> Vec<(usize, &'a str)> {
+    let mut pieces: Vec<(usize, &str)> = vec![];
+    let mut start = 0;
+    for piece in text.split(sep) {
+        pieces.push((text[..start].chars().count() + 1, piece));
+        start += piece.len() + sep.len();
+    }
+    pieces
+}
+
+/// The pieces of `text` separated by runs of whitespace, each with the column it starts at.
+pub fn split_whitespace_with_columns(text: &str) -> Vec<(usize, &str)> {
+    let mut pieces: Vec<(usize, &str)> = vec![];
+    let mut start: Option<(usize, usize)> = None;
+    for (column, (index, c)) in text.char_indices().enumerate() {
+        match (c.is_whitespace(), start) {
+            (false, None) => start = Some((column + 1, index)),
+            (true, Some((first_column, first_index))) => {
+                pieces.push((first_column, &text[first_index..index]));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some((first_column, first_index)) = start {
+        pieces.push((first_column, &text[first_index..]));
+    }
+    pieces
+}
+
+/// `token` read as a `T`, or a problem at `(line, column)` naming the expected `what`.
+pub fn check_value<T: FromStr>(line: usize, column: usize, token: &str, what: &str) -> Result<T, Problem> {
+    token.parse().map_err(|_| Problem::new(line, column, format!("expected {}, found '{}'", what, token)))
+}
+
+/// Checks that `input` is a non-empty rectangular grid made of the characters accepted by `allowed`,
+/// `expected` describing them. Returns the grid's rows along with the problems.
+pub fn check_grid(input: &str, allowed: impl Fn(char) -> bool, expected: &str) -> (Vec<Vec<char>>, Vec<Problem>) {
+    let mut problems: Vec<Problem> = vec![];
+    let rows: Vec<Vec<char>> = parse::lines(input).map(|line| line.text.chars().collect()).collect();
+    match rows.first() {
+        None => problems.push(Problem::new(1, 0, "the grid is empty")),
+        Some(first) if first.is_empty() => problems.push(Problem::new(1, 0, "the first row is empty")),
+        Some(first) => {
+            for (index, row) in rows.iter().enumerate().skip(1) {
+                if row.len() != first.len() {
+                    problems.push(Problem::new(index + 1, 0, format!(
+                        "row has {} cells while the first one has {}", row.len(), first.len()
+                    )));
+                }
+            }
+        }
+    }
+    for (index, row) in rows.iter().enumerate() {
+        for (column, &c) in row.iter().enumerate() {
+            if !allowed(c) {
+                problems.push(Problem::new(index + 1, column + 1, format!("expected {}, found '{}'", expected, c)));
+            }
+        }
+    }
+    problems.sort_by_key(|problem| (problem.line, problem.column));
+    (rows, problems)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_with_columns_counts_characters() {
+        assert_eq!(split_with_columns("12   345", "   "), vec![(1, "12"), (6, "345")]);
+        assert_eq!(split_with_columns("é,b", ","), vec![(1, "é"), (3, "b")]);
+    }
+
+    #[test]
+    fn split_whitespace_with_columns_skips_runs() {
+        assert_eq!(split_whitespace_with_columns(" 12 \t345  "), vec![(2, "12"), (6, "345")]);
+        assert_eq!(split_whitespace_with_columns("é\tb"), vec![(1, "é"), (3, "b")]);
+        assert!(split_whitespace_with_columns("  ").is_empty());
+    }
+
+    #[test]
+    fn check_grid_reports_every_problem() {
+        let (_, problems) = check_grid("ab\nabc\nxb", |c| c == 'a' || c == 'b', "'a' or 'b'");
+        assert_eq!(problems, vec![
+            Problem::new(2, 0, "row has 3 cells while the first one has 2"),
+            Problem::new(2, 3, "expected 'a' or 'b', found 'c'"),
+            Problem::new(3, 1, "expected 'a' or 'b', found 'x'")
+        ]);
+        assert_eq!(check_grid("", |_| true, "").1, vec![Problem::new(1, 0, "the grid is empty")]);
+    }
+}
@@ -0,0 +1,108 @@
+use std::fmt;
+
+use serde::Serialize;
+
+/*
+    Explanations of how an answer was found, for `aoc run --explain`: the operators solving each
+    equation of day 7, the obstacles making day 6's guard loop, and so on. Days build them from the
+    same helpers as their solvers, so that explaining never changes how an answer is computed.
+*/
+
+/// One fact behind an answer, e.g. an equation and how it is solved.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Step {
+    pub summary: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>
+}
+
+impl Step {
+    pub fn new(summary: impl Into<String>, details: Vec<String>) -> Step {
+        Step { summary: summary.into(), details }
+    }
+}
+
+/// An answer along with the steps leading to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Explanation {
+    pub day: u8,
+    pub part: u8,
+    pub answer: i64,
+    pub steps: Vec<Step>
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "Day {} part {}: {}", self.day, self.part, self.answer)?;
+        for step in &self.steps {
+            writeln!(f, "  - {}", step.summary)?;
+            for detail in &step.details {
+                writeln!(f, "      {}", detail)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Text,
+    Json
+}
+
+impl std::str::FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("unknown format '{}', expected text or json", s))
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json")
+        }
+    }
+}
+
+/// The explanations one after the other as text, or as a JSON array.
+pub fn render(explanations: &[Explanation], format: Format) -> String {
+    match format {
+        Format::Text => explanations.iter().map(|explanation| explanation.to_string()).collect(),
+        Format::Json => serde_json::to_string_pretty(explanations).expect("explanations serialize to JSON") + "\n"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explanation() -> Explanation {
+        Explanation {
+            day: 7,
+            part: 1,
+            answer: 190,
+            steps: vec![Step::new("190 = 10 * 19", vec!["10 * 19 = 190".to_string()]), Step::new("no details", vec![])]
+        }
+    }
+
+    #[test]
+    fn renders_as_text() {
+        assert_eq!(render(&[explanation()], Format::Text),
+                   "Day 7 part 1: 190\n  - 190 = 10 * 19\n      10 * 19 = 190\n  - no details\n");
+    }
+
+    #[test]
+    fn renders_as_json() {
+        let json: serde_json::Value = serde_json::from_str(&render(&[explanation()], Format::Json)).unwrap();
+        assert_eq!(json[0]["answer"], 190);
+        assert_eq!(json[0]["steps"][0]["details"][0], "10 * 19 = 190");
+        assert!(json[0]["steps"][1].get("details").is_none());
+    }
+}
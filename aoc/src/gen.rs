@@ -0,0 +1,348 @@
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+/*
+    Seeded random inputs in the format of each day, to stress-test and profile the solvers on bigger
+    inputs than the ones we are given. The same (day, seed, size) always produces the same bytes, so
+    the generator is a small SplitMix64 rather than a crate whose streams may change between versions.
+*/
+
+/// SplitMix64 pseudo-random generator.
+pub struct Rng {
+    state: u64
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        Rng { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E3779B97F4A7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+        z ^ (z >> 31)
+    }
+
+    /// Uniform value in `low..high`.
+    pub fn range(&mut self, low: i64, high: i64) -> i64 {
+        assert!(low < high, "empty range {}..{}", low, high);
+        low + (self.next_u64() % (high - low) as u64) as i64
+    }
+
+    /// Uniform index in `0..n`.
+    pub fn index(&mut self, n: usize) -> usize {
+        self.range(0, n as i64) as usize
+    }
+
+    /// True with probability `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.next_u64() % denominator < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.index(i + 1));
+        }
+    }
+}
+
+pub const DAYS: [u8; 8] = [1, 2, 3, 4, 5, 6, 7, 8];
+
+/// Size giving an input comparable to the real one: number of lines, or grid side for grid days.
+pub fn default_size(day: u8) -> usize {
+    match day {
+        1 | 2 => 1000,
+        3 => 6,
+        4 => 140,
+        5 => 200,
+        6 => 130,
+        7 => 850,
+        _ => 50
+    }
+}
+
+/// Writes a random input for `day` to `out`.
+pub fn generate(day: u8, seed: u64, size: usize, out: &mut impl Write) -> io::Result<()> {
+    // Mix the day in so that two days never share a stream for the same seed.
+    let mut rng = Rng::new(seed ^ ((day as u64) << 56));
+    match day {
+        1 => location_lists(&mut rng, size, out),
+        2 => level_reports(&mut rng, size, out),
+        3 => corrupted_memory(&mut rng, size, out),
+        4 => word_grid(&mut rng, size, out),
+        5 => page_ordering(&mut rng, size, out),
+        6 => guard_map(&mut rng, size, out),
+        7 => calibration_equations(&mut rng, size, out),
+        8 => antenna_map(&mut rng, size, out),
+        _ => Err(io::Error::new(io::ErrorKind::InvalidInput, format!("no generator for day {}", day)))
+    }
+}
+
+/// Writes the rows of a grid, without a newline after the last one like the real inputs.
+fn write_grid(rows: &[Vec<u8>], out: &mut impl Write) -> io::Result<()> {
+    for (i, row) in rows.iter().enumerate() {
+        if i > 0 {
+            out.write_all(b"\n")?;
+        }
+        out.write_all(row)?;
+    }
+    Ok(())
+}
+
+fn location_lists(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    // Draw right-hand IDs from the left list now and then, so that similarity is not always zero.
+    let mut recent: Vec<i64> = vec![];
+    for _ in 0..size {
+        let left = rng.range(10000, 100000);
+        let right = if !recent.is_empty() && rng.chance(1, 3) { *rng.choose(&recent) } else { rng.range(10000, 100000) };
+        if recent.len() < 64 {
+            recent.push(left);
+        } else {
+            let slot = rng.index(recent.len());
+            recent[slot] = left;
+        }
+        writeln!(out, "{}   {}", left, right)?;
+    }
+    Ok(())
+}
+
+fn level_reports(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    for _ in 0..size {
+        let n_levels = rng.range(5, 9) as usize;
+        let increasing = rng.chance(1, 2);
+        let mut levels: Vec<i64> = vec![rng.range(1, 100)];
+        for _ in 1..n_levels {
+            let step = rng.range(1, 4);
+            let previous = *levels.last().unwrap();
+            levels.push(if increasing { previous + step } else { previous - step });
+        }
+        // Break about half of the reports with one or two bad levels.
+        for _ in 0..rng.range(0, 3) {
+            if rng.chance(1, 2) {
+                let position = rng.index(levels.len());
+                levels[position] += rng.range(-4, 5);
+            }
+        }
+        let line: Vec<String> = levels.iter().map(|l| l.to_string()).collect();
+        writeln!(out, "{}", line.join(" "))?;
+    }
+    Ok(())
+}
+
+fn corrupted_memory(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    const NOISE: &[u8] = b"!@#$%^&*()[]{}<>+-?/;:' ,_~mulwhydon'tselectfrom";
+    for _ in 0..size {
+        let mut line = String::new();
+        while line.len() < 3000 {
+            let a = rng.range(1, 1000);
+            let b = rng.range(1, 1000);
+            match rng.range(0, 12) {
+                0..=4 => line += &format!("mul({},{})", a, b),
+                5 => line += "do()",
+                6 => line += "don't()",
+                7 => line += &format!("mul({},{}]", a, b),
+                8 => line += &format!("mul( {},{})", a, b),
+                9 => line += &format!("mul({}, {})", a, b),
+                _ => {}
+            }
+            for _ in 0..rng.range(0, 8) {
+                line.push(*rng.choose(NOISE) as char);
+            }
+        }
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+fn word_grid(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    let rows: Vec<Vec<u8>> = (0..size)
+        .map(|_| (0..size).map(|_| *rng.choose(b"XMAS")).collect())
+        .collect();
+    write_grid(&rows, out)
+}
+
+fn page_ordering(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    /*
+        Like the real input, the rules form a tournament with cycles: pages sit on a circle and each
+        one must come before the next HALF pages. Any set of pages within an arc of HALF + 1 pages is
+        then totally ordered, which is what the updates are drawn from.
+    */
+    const HALF: usize = 24;
+    let mut pages: Vec<i64> = (10..100).collect();
+    rng.shuffle(&mut pages);
+    pages.truncate(2 * HALF + 1);
+    let n_pages = pages.len();
+
+    let mut rules: Vec<(i64, i64)> = vec![];
+    for i in 0..n_pages {
+        for offset in 1..=HALF {
+            rules.push((pages[i], pages[(i + offset) % n_pages]));
+        }
+    }
+    rng.shuffle(&mut rules);
+    for (before, after) in rules {
+        writeln!(out, "{}|{}", before, after)?;
+    }
+    writeln!(out)?;
+
+    for _ in 0..size {
+        let start = rng.index(n_pages);
+        let mut arc: Vec<i64> = (0..=HALF).map(|offset| pages[(start + offset) % n_pages]).collect();
+        let n_update = 2 * rng.range(2, 12) as usize + 1;
+        while arc.len() > n_update {
+            let position = rng.index(arc.len());
+            arc.remove(position);
+        }
+        if rng.chance(1, 2) {
+            rng.shuffle(&mut arc);
+        }
+        let line: Vec<String> = arc.iter().map(|p| p.to_string()).collect();
+        writeln!(out, "{}", line.join(","))?;
+    }
+    Ok(())
+}
+
+/// Whether a guard starting at `(row, col)` facing up eventually walks off the map.
+fn guard_escapes(rows: &[Vec<u8>], row: usize, col: usize) -> bool {
+    const STEPS: [(i64, i64); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+    let size = rows.len() as i64;
+    let (mut row, mut col, mut direction) = (row as i64, col as i64, 0);
+    let mut seen: HashSet<(i64, i64, usize)> = HashSet::new();
+    while seen.insert((row, col, direction)) {
+        let (next_row, next_col) = (row + STEPS[direction].0, col + STEPS[direction].1);
+        if next_row < 0 || next_row >= size || next_col < 0 || next_col >= size {
+            return true;
+        }
+        if rows[next_row as usize][next_col as usize] == b'#' {
+            direction = (direction + 1) % 4;
+        } else {
+            (row, col) = (next_row, next_col);
+        }
+    }
+    false
+}
+
+fn guard_map(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    // Like the real inputs, the guard must leave the map, otherwise part 1 has no answer.
+    let size = size.max(1);
+    loop {
+        let mut rows: Vec<Vec<u8>> = (0..size)
+            .map(|_| (0..size).map(|_| if rng.chance(1, 10) { b'#' } else { b'.' }).collect())
+            .collect();
+        let (row, col) = (rng.index(size), rng.index(size));
+        rows[row][col] = b'^';
+        if guard_escapes(&rows, row, col) {
+            return write_grid(&rows, out);
+        }
+    }
+}
+
+fn calibration_equations(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    // Keep the concatenation of all terms below 16 digits so that every candidate fits in an i64.
+    const MAX_DIGITS: usize = 15;
+    for _ in 0..size {
+        let n_terms = rng.range(2, 13) as usize;
+        let mut terms: Vec<i64> = vec![];
+        let mut digits = 0;
+        while terms.len() < n_terms {
+            let term = if rng.chance(2, 3) { rng.range(1, 10) } else { rng.range(10, 1000) };
+            let term_digits = term.to_string().len();
+            if digits + term_digits > MAX_DIGITS {
+                break;
+            }
+            digits += term_digits;
+            terms.push(term);
+        }
+        if terms.len() < 2 {
+            terms.push(rng.range(1, 10));
+        }
+        let result = if rng.chance(1, 2) {
+            // Solvable: apply random operators.
+            terms[1..].iter().fold(terms[0], |acc, term| match rng.range(0, 3) {
+                0 => acc + term,
+                1 => acc * term,
+                _ => format!("{}{}", acc, term).parse().unwrap()
+            })
+        } else {
+            rng.range(1, 10_i64.pow(digits.min(12) as u32))
+        };
+        let line: Vec<String> = terms.iter().map(|t| t.to_string()).collect();
+        writeln!(out, "{}: {}", result, line.join(" "))?;
+    }
+    Ok(())
+}
+
+fn antenna_map(rng: &mut Rng, size: usize, out: &mut impl Write) -> io::Result<()> {
+    const FREQUENCIES: &[u8] = b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+    let size = size.max(1);
+    let mut rows: Vec<Vec<u8>> = vec![vec![b'.'; size]; size];
+    let mut used: HashSet<(usize, usize)> = HashSet::new();
+    let n_frequencies = (size * size / 60).clamp(1, FREQUENCIES.len());
+    for &frequency in &FREQUENCIES[..n_frequencies] {
+        for _ in 0..rng.range(2, 5) {
+            let cell = (rng.index(size), rng.index(size));
+            if used.insert(cell) {
+                rows[cell.0][cell.1] = frequency;
+            }
+        }
+    }
+    write_grid(&rows, out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::{self, day_1, day_2, day_3, day_4, day_5, day_6, day_7, day_8};
+    use crate::parse::ParseResult;
+
+    // Small inputs keep the tests fast in debug builds.
+    fn generated(day: u8, seed: u64) -> String {
+        let mut bytes: Vec<u8> = vec![];
+        generate(day, seed, default_size(day).min(30), &mut bytes).unwrap();
+        String::from_utf8(bytes).unwrap()
+    }
+
+    fn parse(day: u8, input: &str) -> ParseResult<()> {
+        match day {
+            1 => day_1::parse(input).map(|_| ()),
+            2 => day_2::parse(input).map(|_| ()),
+            3 => day_3::parse(input).map(|_| ()),
+            4 => day_4::parse(input).map(|_| ()),
+            5 => day_5::parse(input).map(|_| ()),
+            6 => day_6::parse(input).map(|_| ()),
+            7 => day_7::parse(input).map(|_| ()),
+            _ => day_8::parse(input).map(|_| ())
+        }
+    }
+
+    #[test]
+    fn seeds_determine_the_input() {
+        for day in DAYS {
+            assert_eq!(generated(day, 7), generated(day, 7), "day {}", day);
+            assert_ne!(generated(day, 7), generated(day, 8), "day {}", day);
+        }
+    }
+
+    #[test]
+    fn generated_inputs_parse() {
+        for day in DAYS {
+            for seed in 0..5 {
+                let input = generated(day, seed);
+                assert_eq!(parse(day, &input), Ok(()), "day {} seed {}", day, seed);
+                let solution = days::solution(day).unwrap();
+                assert_eq!((solution.check_input)(&input), vec![], "day {} seed {}", day, seed);
+            }
+        }
+    }
+
+    #[test]
+    fn unknown_days_have_no_generator() {
+        assert_eq!(generate(9, 0, 10, &mut vec![]).unwrap_err().kind(), io::ErrorKind::InvalidInput);
+    }
+}
@@ -0,0 +1,351 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::hash::Hash;
+
+/// A directed graph with nodes kept in insertion order, so that every traversal is deterministic.
+#[derive(Debug, Clone)]
+pub struct DiGraph<N> {
+    nodes: Vec<N>,
+    indices: HashMap<N, usize>,
+    successors: Vec<Vec<usize>>
+}
+
+/// Nodes forming a cycle, the last one having an edge back to the first.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: fmt::Display> fmt::Display for Cycle<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let nodes: Vec<String> = self.0.iter().map(|n| n.to_string()).collect();
+        write!(f, "cycle {}", nodes.join(" -> "))?;
+        if let Some(first) = self.0.first() {
+            write!(f, " -> {}", first)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Mark {
+    Unvisited,
+    InProgress,
+    Done
+}
+
+impl<N: Eq + Hash + Clone> Default for DiGraph<N> {
+    fn default() -> Self {
+        DiGraph::new()
+    }
+}
+
+impl<N: Eq + Hash + Clone> DiGraph<N> {
+    pub fn new() -> DiGraph<N> {
+        DiGraph { nodes: vec![], indices: HashMap::new(), successors: vec![] }
+    }
+
+    /// Adds `node` if missing and returns its index.
+    pub fn add_node(&mut self, node: N) -> usize {
+        if let Some(&index) = self.indices.get(&node) {
+            return index;
+        }
+        let index = self.nodes.len();
+        self.indices.insert(node.clone(), index);
+        self.nodes.push(node);
+        self.successors.push(vec![]);
+        index
+    }
+
+    /// Adds the edge `from -> to`, creating both nodes if needed. Duplicate edges are ignored.
+    pub fn add_edge(&mut self, from: N, to: N) {
+        let from = self.add_node(from);
+        let to = self.add_node(to);
+        if !self.successors[from].contains(&to) {
+            self.successors[from].push(to);
+        }
+    }
+
+    pub fn contains_node(&self, node: &N) -> bool {
+        self.indices.contains_key(node)
+    }
+
+    pub fn contains_edge(&self, from: &N, to: &N) -> bool {
+        match (self.indices.get(from), self.indices.get(to)) {
+            (Some(&from), Some(&to)) => self.successors[from].contains(&to),
+            _ => false
+        }
+    }
+
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.successors.iter().map(|s| s.len()).sum()
+    }
+
+    pub fn nodes(&self) -> impl Iterator<Item = &N> {
+        self.nodes.iter()
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (&N, &N)> {
+        self.successors.iter().enumerate()
+            .flat_map(move |(from, tos)| tos.iter().map(move |&to| (&self.nodes[from], &self.nodes[to])))
+    }
+
+    pub fn successors(&self, node: &N) -> impl Iterator<Item = &N> {
+        let indices: &[usize] = match self.indices.get(node) {
+            Some(&index) => &self.successors[index],
+            None => &[]
+        };
+        indices.iter().map(move |&i| &self.nodes[i])
+    }
+
+    fn in_degrees(&self) -> Vec<usize> {
+        let mut in_degrees = vec![0; self.nodes.len()];
+        self.successors.iter().flatten().for_each(|&to| in_degrees[to] += 1);
+        in_degrees
+    }
+
+    fn to_nodes(&self, indices: &[usize]) -> Vec<N> {
+        indices.iter().map(|&i| self.nodes[i].clone()).collect()
+    }
+
+    /// Topological order using Kahn's algorithm; ties are broken by insertion order.
+    pub fn topological_sort_kahn(&self) -> Result<Vec<N>, Cycle<N>> {
+        let mut in_degrees = self.in_degrees();
+        let mut queue: VecDeque<usize> = (0..self.nodes.len()).filter(|&i| in_degrees[i] == 0).collect();
+        let mut order: Vec<usize> = Vec::with_capacity(self.nodes.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &next in &self.successors[node] {
+                in_degrees[next] -= 1;
+                if in_degrees[next] == 0 {
+                    queue.push_back(next);
+                }
+            }
+        }
+        if order.len() == self.nodes.len() {
+            Ok(self.to_nodes(&order))
+        } else {
+            Err(self.find_cycle().expect("nodes left over by Kahn's algorithm lie on a cycle"))
+        }
+    }
+
+    /// Topological order using reversed DFS post-order.
+    pub fn topological_sort_dfs(&self) -> Result<Vec<N>, Cycle<N>> {
+        let mut marks = vec![Mark::Unvisited; self.nodes.len()];
+        let mut post_order: Vec<usize> = Vec::with_capacity(self.nodes.len());
+        for start in 0..self.nodes.len() {
+            if let Some(cycle) = self.dfs(start, &mut marks, &mut post_order) {
+                return Err(Cycle(self.to_nodes(&cycle)));
+            }
+        }
+        post_order.reverse();
+        Ok(self.to_nodes(&post_order))
+    }
+
+    /// Any cycle in the graph, or `None` when it is acyclic.
+    pub fn find_cycle(&self) -> Option<Cycle<N>> {
+        let mut marks = vec![Mark::Unvisited; self.nodes.len()];
+        let mut post_order: Vec<usize> = vec![];
+        (0..self.nodes.len())
+            .find_map(|start| self.dfs(start, &mut marks, &mut post_order))
+            .map(|cycle| Cycle(self.to_nodes(&cycle)))
+    }
+
+    /// Iterative DFS from `start` pushing finished nodes to `post_order`. Returns the cycle found, if any.
+    fn dfs(&self, start: usize, marks: &mut [Mark], post_order: &mut Vec<usize>) -> Option<Vec<usize>> {
+        if marks[start] != Mark::Unvisited {
+            return None;
+        }
+        // Stack of (node, index of the next successor to visit); it is also the current path.
+        let mut stack: Vec<(usize, usize)> = vec![(start, 0)];
+        marks[start] = Mark::InProgress;
+        while let Some(&mut (node, ref mut next)) = stack.last_mut() {
+            if let Some(&successor) = self.successors[node].get(*next) {
+                *next += 1;
+                match marks[successor] {
+                    Mark::Unvisited => {
+                        marks[successor] = Mark::InProgress;
+                        stack.push((successor, 0));
+                    }
+                    Mark::InProgress => {
+                        let position = stack.iter().position(|&(n, _)| n == successor).unwrap();
+                        return Some(stack[position..].iter().map(|&(n, _)| n).collect());
+                    }
+                    Mark::Done => {}
+                }
+            } else {
+                marks[node] = Mark::Done;
+                post_order.push(node);
+                stack.pop();
+            }
+        }
+        None
+    }
+
+    /// The subgraph made of `nodes` (in the given order) and the edges between them.
+    /// Nodes unknown to this graph are kept as isolated nodes, and repeated nodes appear once.
+    pub fn induced_subgraph<'a>(&self, nodes: impl IntoIterator<Item = &'a N>) -> DiGraph<N> where N: 'a {
+        let mut subgraph = DiGraph::new();
+        let mut kept_set: HashSet<usize> = HashSet::new();
+        let mut kept: Vec<usize> = vec![];
+        for node in nodes {
+            subgraph.add_node(node.clone());
+            if let Some(&index) = self.indices.get(node) {
+                if kept_set.insert(index) {
+                    kept.push(index);
+                }
+            }
+        }
+        for &from in &kept {
+            for &to in self.successors[from].iter().filter(|to| kept_set.contains(to)) {
+                subgraph.add_edge(self.nodes[from].clone(), self.nodes[to].clone());
+            }
+        }
+        subgraph
+    }
+
+    fn reachable_from(&self, start: usize) -> Vec<bool> {
+        let mut reached = vec![false; self.nodes.len()];
+        let mut stack: Vec<usize> = self.successors[start].clone();
+        while let Some(node) = stack.pop() {
+            if !reached[node] {
+                reached[node] = true;
+                stack.extend(&self.successors[node]);
+            }
+        }
+        reached
+    }
+
+    /// Graph with an edge `a -> b` whenever `b` can be reached from `a`.
+    pub fn transitive_closure(&self) -> DiGraph<N> {
+        let mut closure = DiGraph::new();
+        self.nodes.iter().for_each(|n| { closure.add_node(n.clone()); });
+        for from in 0..self.nodes.len() {
+            for (to, reached) in self.reachable_from(from).into_iter().enumerate() {
+                if reached {
+                    closure.add_edge(self.nodes[from].clone(), self.nodes[to].clone());
+                }
+            }
+        }
+        closure
+    }
+
+    /// Smallest graph with the same reachability. Only defined for acyclic graphs.
+    pub fn transitive_reduction(&self) -> Result<DiGraph<N>, Cycle<N>> {
+        if let Some(cycle) = self.find_cycle() {
+            return Err(cycle);
+        }
+        let reachable: Vec<Vec<bool>> = (0..self.nodes.len()).map(|i| self.reachable_from(i)).collect();
+        let mut reduction = DiGraph::new();
+        self.nodes.iter().for_each(|n| { reduction.add_node(n.clone()); });
+        for from in 0..self.nodes.len() {
+            for &to in &self.successors[from] {
+                let is_redundant = self.successors[from].iter()
+                    .any(|&other| other != to && reachable[other][to]);
+                if !is_redundant {
+                    reduction.add_edge(self.nodes[from].clone(), self.nodes[to].clone());
+                }
+            }
+        }
+        Ok(reduction)
+    }
+}
+
+impl<N: Eq + Hash + Clone + fmt::Display> DiGraph<N> {
+    /// Graphviz DOT representation, e.g. for `dot -Tsvg`.
+    pub fn to_dot(&self, name: &str) -> String {
+        let quote = |s: &dyn fmt::Display| format!("\"{}\"", s.to_string().replace('\\', "\\\\").replace('"', "\\\""));
+        let mut dot = format!("digraph {} {{\n", quote(&name));
+        for node in &self.nodes {
+            dot += &format!("    {};\n", quote(node));
+        }
+        for (from, to) in self.edges() {
+            dot += &format!("    {} -> {};\n", quote(from), quote(to));
+        }
+        dot += "}\n";
+        dot
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph(edges: &[(u8, u8)]) -> DiGraph<u8> {
+        let mut graph = DiGraph::new();
+        for &(from, to) in edges {
+            graph.add_edge(from, to);
+        }
+        graph
+    }
+
+    /// Whether `order` holds every node once, each before its successors.
+    fn is_topological(graph: &DiGraph<u8>, order: &[u8]) -> bool {
+        let position: HashMap<u8, usize> = order.iter().enumerate().map(|(i, &node)| (node, i)).collect();
+        order.len() == graph.node_count() && position.len() == order.len()
+            && graph.edges().all(|(from, to)| position[from] < position[to])
+    }
+
+    fn edge_set(graph: &DiGraph<u8>) -> HashSet<(u8, u8)> {
+        graph.edges().map(|(&from, &to)| (from, to)).collect()
+    }
+
+    const DIAMOND: [(u8, u8); 6] = [(1, 2), (1, 3), (2, 4), (3, 4), (1, 4), (4, 5)];
+
+    #[test]
+    fn both_sorts_give_valid_orders() {
+        let diamond = graph(&DIAMOND);
+        let kahn = diamond.topological_sort_kahn().unwrap();
+        let dfs = diamond.topological_sort_dfs().unwrap();
+        assert_eq!(kahn, vec![1, 2, 3, 4, 5]);
+        assert!(is_topological(&diamond, &kahn));
+        assert!(is_topological(&diamond, &dfs), "{:?}", dfs);
+        assert_eq!(diamond.find_cycle(), None);
+        assert_eq!(DiGraph::<u8>::new().topological_sort_dfs(), Ok(vec![]));
+    }
+
+    #[test]
+    fn cyclic_graphs_return_a_cycle() {
+        let cyclic = graph(&[(1, 2), (2, 3), (3, 4), (4, 2), (0, 1)]);
+        let cycle = Cycle(vec![2, 3, 4]);
+        assert_eq!(cyclic.find_cycle(), Some(cycle.clone()));
+        assert_eq!(cyclic.topological_sort_dfs(), Err(cycle.clone()));
+        assert_eq!(cyclic.topological_sort_kahn(), Err(cycle.clone()));
+        assert_eq!(cyclic.transitive_reduction().unwrap_err(), cycle);
+        assert_eq!(Cycle(vec![2, 3, 4]).to_string(), "cycle 2 -> 3 -> 4 -> 2");
+        assert_eq!(graph(&[(7, 7)]).find_cycle(), Some(Cycle(vec![7])));
+    }
+
+    #[test]
+    fn reduction_and_closure_round_trip() {
+        let diamond = graph(&DIAMOND);
+        let closure = diamond.transitive_closure();
+        let reduction = diamond.transitive_reduction().unwrap();
+        assert_eq!(edge_set(&reduction), HashSet::from([(1, 2), (1, 3), (2, 4), (3, 4), (4, 5)]));
+        assert_eq!(closure.edge_count(), 9);
+        assert_eq!(edge_set(&reduction.transitive_closure()), edge_set(&closure));
+        assert_eq!(edge_set(&closure.transitive_reduction().unwrap()), edge_set(&reduction));
+    }
+
+    #[test]
+    fn induced_subgraph_keeps_the_edges_between_its_nodes() {
+        let diamond = graph(&DIAMOND);
+        let subgraph = diamond.induced_subgraph(&[4, 1, 9, 2, 1]);
+        assert_eq!(subgraph.nodes().copied().collect::<Vec<u8>>(), vec![4, 1, 9, 2]);
+        assert_eq!(edge_set(&subgraph), HashSet::from([(1, 4), (1, 2), (2, 4)]));
+    }
+
+    #[test]
+    fn dot_output_escapes_labels() {
+        let mut labels: DiGraph<String> = DiGraph::new();
+        labels.add_edge("say \"hi\"".to_string(), "C:\\dir".to_string());
+        assert_eq!(labels.to_dot("a\\b"), r#"digraph "a\\b" {
+    "say \"hi\"";
+    "C:\\dir";
+    "say \"hi\"" -> "C:\\dir";
+}
+"#);
+    }
+}
@@ -1 +1,20 @@
+// Lets code generated by `aoc_derive` refer to `::aoc` from inside this crate too.
+extern crate self as aoc;
+
 pub mod utils;
+pub mod parse;
+pub mod diagnostics;
+pub mod explain;
+pub mod graph;
+pub mod combinatorics;
+pub mod order_statistics;
+pub mod cli;
+pub mod config;
+pub mod gen;
+pub mod days;
+pub mod cross_check;
+pub mod snapshot;
+pub mod bench;
+pub mod runner;
+
+pub use aoc_derive::FromInput;
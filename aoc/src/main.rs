@@ -0,0 +1,296 @@
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::process::ExitCode;
+
+use aoc::cli::Args;
+use aoc::config::{self, Config};
+use aoc::bench::{self, Change, Duration, Stats};
+use aoc::days::{self, day_1, SOLUTIONS};
+use aoc::{cross_check, gen, runner, snapshot};
+
+const USAGE: &str = "Usage:
+    aoc run <day> [input] [--explain] [--stream [--memory-limit IDS]] [--metric NAMES]
+            [--report table|csv [--sort id|contribution]] [--matrix] [--removals K]
+            [--violations]
+        Solves both parts of <day> on data/day_<day>/<input>.txt (`input` by default) or on a path.
+        With --explain, also shows the steps behind each answer (days 2, 5, 6, 7 and 8) in the
+        output format. With --stream, day 1 reads its input line by line and sorts lists of more
+        than IDS location IDs (16777216 by default) in runs on disk. With --metric, day 1 compares
+        its lists with each of the comma-separated metrics: distance, similarity, squared-l2,
+        footrule, kendall-tau and jaccard. With --report, day 1 breaks its similarity score down by
+        ID, sorted by ID or by decreasing contribution, and lists the IDs found in one list only.
+        With --matrix, day 1 reads any number of lists, one per column, and shows the distance
+        and similarity of each pair and the distance of all of them to their median. With
+        --removals, day 2 counts the reports made safe by removing at most k levels, for each k
+        from 0 to K. With --violations, day 2 counts its unsafe reports by the first rule they
+        break, and how many of them removing one level makes safe. These options exclude each
+        other.
+    aoc gen <day> [--seed S] [--size N] [--output PATH]
+        Writes a random input for <day>, by default to data/day_<day>/gen_<S>_<N>.txt.
+        Run a day on it with `aoc run <day> gen_<S>_<N>`.
+    aoc check-input <day> [input]
+        Checks data/day_<day>/<input>.txt (or a path) against the day's input invariants and
+        reports every problem found, without running the solvers.
+    aoc cross-check [day] [--generated N] [--size S]
+        Compares the reference and optimized implementations of a part on the example, the real
+        input and N generated inputs (10 by default), and reports the first disagreement.
+    aoc snapshot [day] [--accept]
+        Compares rendered maps with their snapshots in data/day_<day>/*.snap and shows a diff for
+        the ones that changed. With --accept, writes the current renderings instead.
+    aoc bench [day] [--input NAME] [--samples N] [--save-baseline NAME] [--compare NAME] [--threshold PCT]
+        Times both parts on an input (`input` by default) over N runs (10 by default). Saves the
+        medians as <NAME>.tsv in the baseline directory, or compares them with it and fails if a
+        part got slower by more than PCT percent (10 by default). Build with --release for
+        meaningful numbers.
+    aoc config show
+        Prints the effective settings and where each one comes from.
+
+Settings, read from aoc.toml (or --config PATH) and overridden by the AOC_<NAME> environment
+variables and then by these options of every command:
+    --data-dir DIR                 directory of the day_<day> inputs (data)
+    --year YEAR                    puzzle year (2024)
+    --session-token env:NAME|file:PATH
+                                   where to find the website's session token (env:AOC_SESSION)
+    --input NAME                   input used when a command isn't given one (input)
+    --baseline-dir DIR             directory of the benchmark baselines (bench)
+    --timeout SECS                 time limit of each part, 0 for none (0)
+    --format text|json             output format of explanations (text)
+    --color auto|always|never      colours in diffs and benchmarks (auto)
+How day 1 reads its lists, from the [day_1] section of aoc.toml:
+    --delimiter whitespace|tab|SEP what separates the location IDs of a line (whitespace)
+Day 2's safety policy, from the [day_2] section of aoc.toml:
+    --min-step N, --max-step N     smallest and largest change between adjacent levels (1 and 3)
+    --direction increasing|decreasing|either|non-strict
+                                   which way the levels go, non-strict also allowing equal
+                                   neighbours (either)
+    --min-level N, --max-level N   lowest and highest allowed level (none)";
+
+/// Options of `aoc run` that each pick another way of running a day.
+const RUN_MODES: [&str; 7] = ["--matrix", "--report", "--metric", "--violations", "--removals", "--stream", "--explain"];
+
+fn run_command(args: &Args, config: &Config) -> Result<(), String> {
+    args.exclusive(&RUN_MODES)?;
+    let day: u8 = args.positional(1, "day")?;
+    let variant: &str = args.positional.get(2).unwrap_or(&config.input);
+    let path = config.input_path(day, variant);
+    if args.has("--matrix") {
+        if day != 1 {
+            return Err("only day 1 has several lists".to_string());
+        }
+        runner::run_day_1_matrices(&path, &config.day_1, config.timeout(day))
+    } else if let Some(format) = args.value("--report") {
+        if day != 1 {
+            return Err("only day 1 has a report".to_string());
+        }
+        let format: day_1::ReportFormat = format.parse()?;
+        runner::run_day_1_report(&path, &config.day_1, args.parsed_or("--sort", day_1::ReportOrder::Id)?, format,
+                                 config.timeout(day))
+    } else if let Some(names) = args.value("--metric") {
+        if day != 1 {
+            return Err("only day 1 has metrics".to_string());
+        }
+        let metrics: Vec<day_1::ListMetric> = names.split(',')
+            .map(|name| name.trim().parse())
+            .collect::<Result<_, String>>()?;
+        runner::run_day_1_metrics(&path, &config.day_1, &metrics, config.timeout(day))
+    } else if args.has("--violations") {
+        if day != 2 {
+            return Err("only day 2 has violations".to_string());
+        }
+        runner::run_day_2_violations(&path, &config.day_2, config.timeout(day))
+    } else if args.has("--removals") {
+        if day != 2 {
+            return Err("only day 2 has removals".to_string());
+        }
+        runner::run_day_2_removals(&path, &config.day_2, args.parsed_or("--removals", 1)?, config.timeout(day))
+    } else if args.has("--stream") {
+        if day != 1 {
+            return Err("only day 1 can stream its input".to_string());
+        }
+        runner::run_day_1_streaming(&path, &config.day_1, args.parsed_or("--memory-limit", day_1::DEFAULT_MEMORY_LIMIT)?,
+                                    config.timeout(day))
+    } else if args.has("--explain") {
+        runner::explain(day, &path, config)
+    } else {
+        runner::run_with(day, &path, config)
+    }
+}
+
+fn gen_command(args: &Args, config: &Config) -> Result<(), String> {
+    let day: u8 = args.positional(1, "day")?;
+    if !gen::DAYS.contains(&day) {
+        return Err(format!("no generator for day {}", day));
+    }
+    let seed: u64 = args.parsed_or("--seed", 0)?;
+    let size: usize = args.parsed_or("--size", gen::default_size(day))?;
+    let output: String = match args.value("--output") {
+        Some(path) => path.to_string(),
+        None => format!("{}/day_{}/gen_{}_{}.txt", config.data_dir, day, seed, size)
+    };
+    let file = File::create(&output).map_err(|e| format!("couldn't create {}: {}", output, e))?;
+    let mut writer = BufWriter::new(file);
+    gen::generate(day, seed, size, &mut writer)
+        .and_then(|_| writer.flush())
+        .map_err(|e| format!("couldn't write {}: {}", output, e))?;
+    println!("{}", output);
+    Ok(())
+}
+
+fn check_input_command(args: &Args, config: &Config) -> Result<(), String> {
+    let day: u8 = args.positional(1, "day")?;
+    let variant: &str = args.positional.get(2).unwrap_or(&config.input);
+    let solution = days::solution(day).ok_or_else(|| format!("no solution for day {}", day))?;
+    let path = config.input_path(day, variant);
+    let input = runner::read_input(&path)?;
+    let problems = (solution.check_input)(&input);
+    for problem in &problems {
+        println!("{}: {}", path, problem);
+    }
+    match problems.len() {
+        0 => {
+            println!("{}: no problems found", path);
+            Ok(())
+        }
+        n => Err(format!("{} problem(s) in {}", n, path))
+    }
+}
+
+fn cross_check_command(args: &Args, config: &Config) -> Result<(), String> {
+    let day: Option<u8> = match args.positional.get(1) {
+        Some(_) => Some(args.positional(1, "day")?),
+        None => None
+    };
+    let n_generated: u64 = args.parsed_or("--generated", 10)?;
+    let mut n_disagreements = 0;
+    for variant in cross_check::variants(day) {
+        let size: usize = args.parsed_or("--size", gen::default_size(variant.day))?;
+        let mut inputs = cross_check::file_inputs(config, variant.day);
+        inputs.extend(cross_check::generated_inputs(variant.day, 0..n_generated, size));
+        match cross_check::check(variant, &inputs) {
+            Ok(()) => println!("day {} part {}: agree on {} inputs", variant.day, variant.part, inputs.len()),
+            Err(disagreement) => {
+                println!("{}", disagreement);
+                n_disagreements += 1;
+            }
+        }
+    }
+    match n_disagreements {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) disagree", n))
+    }
+}
+
+fn snapshot_command(args: &Args, config: &Config) -> Result<(), String> {
+    let day: Option<u8> = match args.positional.get(1) {
+        Some(_) => Some(args.positional(1, "day")?),
+        None => None
+    };
+    let mut n_mismatches = 0;
+    for snapshot in snapshot::snapshots(day) {
+        if args.has("--accept") {
+            if snapshot.accept(config)? {
+                println!("updated {}", snapshot.path(config));
+            }
+        } else if let Err(message) = snapshot.check(config) {
+            for line in message.lines() {
+                match line.chars().next() {
+                    Some('-') => println!("{}", config.paint(config::RED, line)),
+                    Some('+') => println!("{}", config.paint(config::GREEN, line)),
+                    _ => println!("{}", line)
+                }
+            }
+            n_mismatches += 1;
+        }
+    }
+    match n_mismatches {
+        0 => Ok(()),
+        n => Err(format!("{} snapshot(s) don't match, run with --accept if the changes are intended", n))
+    }
+}
+
+fn bench_command(args: &Args, config: &Config) -> Result<(), String> {
+    let day: Option<u8> = match args.positional.get(1) {
+        Some(_) => Some(args.positional(1, "day")?),
+        None => None
+    };
+    let samples: usize = args.parsed_or("--samples", 10)?;
+    let threshold: f64 = args.parsed_or("--threshold", 10.0)?;
+    let baseline: Option<Vec<Stats>> = args.value("--compare")
+        .map(|name| bench::load_baseline(&bench::baseline_path(&config.baseline_dir, name)))
+        .transpose()?;
+
+    let mut all_stats: Vec<Stats> = vec![];
+    let mut n_regressions = 0;
+    for solution in SOLUTIONS.iter().filter(|solution| day.is_none_or(|day| solution.day == day)) {
+        let path = config.input_path(solution.day, &config.input);
+        let input = runner::read_input(&path)?;
+        for part in [1, 2] {
+            let stats = bench::measure(solution, part, &input, samples).map_err(|e| format!("{}:{}", path, e))?;
+            let mut line = format!("day {} part {}: {} (min {}, max {})",
+                                   stats.day, part, Duration(stats.median), Duration(stats.min), Duration(stats.max));
+            if let Some(baseline) = &baseline {
+                match baseline.iter().find(|b| b.day == stats.day && b.part == part) {
+                    None => line += ", not in the baseline",
+                    Some(before) => {
+                        let (percent, change) = bench::compare(before, &stats, threshold);
+                        line += &format!(", {:+.1}% from {}", percent, Duration(before.median));
+                        match change {
+                            Change::Slower => {
+                                line += &format!(", {}", config.paint(config::RED, "REGRESSION"));
+                                n_regressions += 1;
+                            }
+                            Change::Faster => line += &format!(", {}", config.paint(config::GREEN, "faster")),
+                            Change::Unchanged => {}
+                        }
+                    }
+                }
+            }
+            println!("{}", line);
+            all_stats.push(stats);
+        }
+    }
+    if let Some(name) = args.value("--save-baseline") {
+        let path = bench::baseline_path(&config.baseline_dir, name);
+        bench::save_baseline(&path, &all_stats)?;
+        println!("saved {}", path);
+    }
+    match n_regressions {
+        0 => Ok(()),
+        n => Err(format!("{} part(s) got slower by more than {}%", n, threshold))
+    }
+}
+
+fn config_command(args: &Args, config: &Config) -> Result<(), String> {
+    match args.positional.get(1).map(String::as_str) {
+        Some("show") => {
+            print!("{}", config.show());
+            Ok(())
+        }
+        _ => Err(USAGE.to_string())
+    }
+}
+
+fn main() -> ExitCode {
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+    let result = Args::parse(&raw_args, &["--accept", "--explain", "--matrix", "--stream", "--violations"]).and_then(|args| {
+        let config = Config::load(&args)?;
+        match args.positional.first().map(String::as_str) {
+            Some("run") => run_command(&args, &config),
+            Some("gen") => gen_command(&args, &config),
+            Some("check-input") => check_input_command(&args, &config),
+            Some("cross-check") => cross_check_command(&args, &config),
+            Some("snapshot") => snapshot_command(&args, &config),
+            Some("bench") => bench_command(&args, &config),
+            Some("config") => config_command(&args, &config),
+            _ => Err(USAGE.to_string())
+        }
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,269 @@
+/*
+    Order-statistic tree over a multiset of i64: a binary trie on the bits of the values, with a
+    node created the first time a value goes through it. Every update or query walks the 64 levels,
+    so values don't need to be known in advance and a new one never rebuilds anything.
+
+    Values are flipped on their sign bit, which orders them as unsigned keys. Each node holds the
+    number of values below it and their sum; nodes emptied by removals are kept for reuse.
+*/
+
+const BITS: u32 = 64;
+
+#[derive(Debug, Clone, Default)]
+struct Node {
+    /// Indices of the children for a 0 and a 1 bit, 0 meaning none since the root is no child.
+    children: [u32; 2],
+    count: u64,
+    sum: i128
+}
+
+#[derive(Debug, Clone)]
+pub struct OrderStatistics {
+    nodes: Vec<Node>
+}
+
+impl Default for OrderStatistics {
+    fn default() -> OrderStatistics {
+        OrderStatistics::new()
+    }
+}
+
+fn key(value: i64) -> u64 {
+    value as u64 ^ 1 << (BITS - 1)
+}
+
+fn bit(key: u64, level: u32) -> usize {
+    (key >> (BITS - 1 - level) & 1) as usize
+}
+
+impl OrderStatistics {
+    pub fn new() -> OrderStatistics {
+        OrderStatistics { nodes: vec![Node::default()] }
+    }
+
+    /// Number of values, counting repeats.
+    pub fn len(&self) -> u64 {
+        self.nodes[0].count
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of nodes allocated so far, at most 64 per distinct value.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn insert(&mut self, value: i64) {
+        let key = key(value);
+        let mut node = 0;
+        for level in 0..BITS {
+            self.nodes[node].count += 1;
+            self.nodes[node].sum += value as i128;
+            let side = bit(key, level);
+            if self.nodes[node].children[side] == 0 {
+                self.nodes[node].children[side] = u32::try_from(self.nodes.len()).expect("fewer than 2^32 nodes");
+                self.nodes.push(Node::default());
+            }
+            node = self.nodes[node].children[side] as usize;
+        }
+        self.nodes[node].count += 1;
+        self.nodes[node].sum += value as i128;
+    }
+
+    /// Removes one occurrence of `value`, returning whether there was one.
+    pub fn remove(&mut self, value: i64) -> bool {
+        if self.count(value) == 0 {
+            return false;
+        }
+        let key = key(value);
+        let mut node = 0;
+        for level in 0..=BITS {
+            self.nodes[node].count -= 1;
+            self.nodes[node].sum -= value as i128;
+            if level < BITS {
+                node = self.nodes[node].children[bit(key, level)] as usize;
+            }
+        }
+        true
+    }
+
+    /// Number of occurrences of `value`.
+    pub fn count(&self, value: i64) -> u64 {
+        let key = key(value);
+        let mut node = 0;
+        for level in 0..BITS {
+            node = self.nodes[node].children[bit(key, level)] as usize;
+            if node == 0 {
+                return 0;
+            }
+        }
+        self.nodes[node].count
+    }
+
+    /// Number of values below `value`.
+    pub fn rank(&self, value: i64) -> u64 {
+        let key = key(value);
+        let (mut node, mut count) = (0, 0);
+        for level in 0..BITS {
+            let children = self.nodes[node].children;
+            if bit(key, level) == 1 && children[0] != 0 {
+                count += self.nodes[children[0] as usize].count;
+            }
+            node = children[bit(key, level)] as usize;
+            if node == 0 {
+                break;
+            }
+        }
+        count
+    }
+
+    /// The value at index `k` once sorted, counting repeats.
+    pub fn nth(&self, k: u64) -> Option<i64> {
+        if k >= self.len() {
+            return None;
+        }
+        let (mut node, mut key, mut remaining) = (0, 0u64, k);
+        for _ in 0..BITS {
+            let [zero, one] = self.nodes[node].children;
+            let below = if zero == 0 { 0 } else { self.nodes[zero as usize].count };
+            key <<= 1;
+            if remaining < below {
+                node = zero as usize;
+            } else {
+                remaining -= below;
+                key |= 1;
+                node = one as usize;
+            }
+        }
+        Some((key ^ 1 << (BITS - 1)) as i64)
+    }
+
+    /// Sum of `|a_k - b_k|` over the values of both trees paired in sorted order, as far as the
+    /// smaller one goes.
+    pub fn paired_distance(&self, other: &OrderStatistics) -> i128 {
+        let paired = self.len().min(other.len());
+        if paired == 0 {
+            return 0;
+        }
+        let sides = [Paired::new(self, paired), Paired::new(other, paired)];
+        walk(&sides, [Some(0), Some(0)], 0, i64::MIN, 0).0
+    }
+}
+
+/*
+    With both trees cut to their `paired` smallest values a and b, the paired distance is the sum
+    over every integer v of |D(v)|, D(v) being the number of a up to v minus that of b. Both tries
+    are walked together over the same ranges of values: where D can't change sign within a range,
+    given its value before the range and the counts in it, the sum over the range follows from the
+    counts and sums of its nodes. Only ranges where the two lists cross are split, so a query costs
+    O(64) per crossing rather than per value.
+*/
+
+/// A tree cut to its `paired` smallest values: those below `cut` and `at_cut` copies of `cut`.
+struct Paired<'a> {
+    tree: &'a OrderStatistics,
+    cut: Option<(i64, u64)>
+}
+
+impl Paired<'_> {
+    fn new(tree: &OrderStatistics, paired: u64) -> Paired<'_> {
+        let cut = (tree.len() > paired).then(|| {
+            let value = tree.nth(paired - 1).expect("the tree has more values than `paired`");
+            (value, paired - tree.rank(value))
+        });
+        Paired { tree, cut }
+    }
+
+    /// Number and sum of the kept values of `node`, which holds the values from `low` to `high`, or
+    /// None when the cut falls inside it.
+    fn stats(&self, node: Option<usize>, low: i64, high: i64) -> Option<(i128, i128)> {
+        let Some(node) = node else {
+            return Some((0, 0));
+        };
+        let full = (self.tree.nodes[node].count as i128, self.tree.nodes[node].sum);
+        match self.cut {
+            None => Some(full),
+            Some((cut, _)) if high < cut => Some(full),
+            Some((cut, _)) if low > cut => Some((0, 0)),
+            Some((cut, at_cut)) if low == high => Some((at_cut as i128, at_cut as i128 * cut as i128)),
+            Some(_) => None
+        }
+    }
+
+    fn children(&self, node: Option<usize>) -> [Option<usize>; 2] {
+        node.map_or([None, None], |node| self.tree.nodes[node].children.map(|child| (child != 0).then_some(child as usize)))
+    }
+}
+
+/// Sum of |D(v)| over the values from `low` covered by `nodes` at `level`, and the change of D
+/// over them, D being `before` just below `low`.
+fn walk(sides: &[Paired; 2], nodes: [Option<usize>; 2], level: u32, low: i64, before: i128) -> (i128, i128) {
+    let width: i128 = 1 << (BITS - level);
+    let high = low as i128 + width - 1;
+    let stats = [sides[0].stats(nodes[0], low, high as i64), sides[1].stats(nodes[1], low, high as i64)];
+    if let [Some((count_a, sum_a)), Some((count_b, sum_b))] = stats {
+        // D keeps one sign within the range: sum D(v) by counting each value once per v from it up.
+        if level == BITS || before - count_b >= 0 || before + count_a <= 0 {
+            let total = before * width + count_a * (high + 1) - sum_a - count_b * (high + 1) + sum_b;
+            return (total.abs(), count_a - count_b);
+        }
+    }
+    let ([low_a, high_a], [low_b, high_b]) = (sides[0].children(nodes[0]), sides[1].children(nodes[1]));
+    let (below, change_below) = walk(sides, [low_a, low_b], level + 1, low, before);
+    let middle = (low as i128 + width / 2) as i64;
+    let (above, change_above) = walk(sides, [high_a, high_b], level + 1, middle, before + change_below);
+    (below + above, change_below + change_above)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn queries_follow_updates() {
+        let mut tree = OrderStatistics::new();
+        for value in [5, -3, 5, i64::MIN, 0, i64::MAX, 5] {
+            tree.insert(value);
+        }
+        assert!(tree.remove(5));
+        assert!(!tree.remove(7));
+        assert_eq!((tree.len(), tree.count(5), tree.count(4)), (6, 2, 0));
+        assert_eq!((tree.rank(5), tree.rank(6), tree.rank(i64::MIN)), (3, 5, 0));
+        assert_eq!((0..7).map(|k| tree.nth(k)).collect::<Vec<_>>(),
+                   [Some(i64::MIN), Some(-3), Some(0), Some(5), Some(5), Some(i64::MAX), None]);
+    }
+
+    #[test]
+    fn a_new_value_only_adds_its_path() {
+        let values: Vec<i64> = (0..200).map(|i| (i * 7919) % 1000 - 500).collect();
+        let mut tree = OrderStatistics::new();
+        for &value in &values {
+            let before = tree.node_count();
+            tree.insert(value);
+            assert!(tree.node_count() - before <= BITS as usize);
+        }
+        // Emptied nodes are reused when their values come back.
+        let before = tree.node_count();
+        assert!(values[..100].iter().all(|&value| tree.remove(value)));
+        values[..100].iter().for_each(|&value| tree.insert(value));
+        assert_eq!((tree.node_count(), tree.len()), (before, 200));
+    }
+
+    #[test]
+    fn paired_distance_pairs_sorted_values() {
+        let tree = |values: &[i64]| {
+            let mut tree = OrderStatistics::new();
+            values.iter().for_each(|&value| tree.insert(value));
+            tree
+        };
+        let (left, right) = (tree(&[3, 4, 2, 1, 3, 3]), tree(&[4, 3, 5, 3, 9, 3]));
+        assert_eq!(left.paired_distance(&right), 11);
+        // The longer tree only pairs its smallest values, repeats included.
+        assert_eq!(tree(&[1, 5, 5, 5]).paired_distance(&tree(&[2, 9])), 5);
+        assert_eq!(tree(&[i64::MIN, i64::MAX]).paired_distance(&tree(&[i64::MAX, i64::MIN])), 0);
+        assert_eq!(tree(&[i64::MIN]).paired_distance(&tree(&[i64::MAX])), u64::MAX as i128);
+        assert_eq!(tree(&[]).paired_distance(&tree(&[1])), 0);
+    }
+}
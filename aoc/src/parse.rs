@@ -0,0 +1,243 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Error raised while parsing puzzle input, pointing at the offending line (1-based).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> ParseError {
+        ParseError { line, message: message.into() }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub type ParseResult<T> = Result<T, ParseError>;
+
+/// One line of input along with its 1-based position in the file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str
+}
+
+/// A block of consecutive non-blank lines.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Section<'a> {
+    pub lines: Vec<Line<'a>>
+}
+
+impl<'a> Section<'a> {
+    /// Line number of the first line of the section, used for errors about the section as a whole.
+    pub fn first_line(&self) -> usize {
+        self.lines.first().map(|line| line.number).unwrap_or(0)
+    }
+}
+
+/// Numbered lines of `input`, with trailing `\r` removed.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(index, text)| Line { number: index + 1, text: text.trim_end_matches('\r') })
+}
+
+/// Splits `input` into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    let mut sections: Vec<Section> = vec![];
+    let mut current: Vec<Line> = vec![];
+    for line in lines(input) {
+        if line.text.trim().is_empty() {
+            if !current.is_empty() {
+                sections.push(Section { lines: std::mem::take(&mut current) });
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        sections.push(Section { lines: current });
+    }
+    sections
+}
+
+fn parse_value<T: FromStr>(number: usize, s: &str) -> ParseResult<T> {
+    s.parse::<T>().map_err(|_| ParseError::new(number, format!("invalid value '{}'", s)))
+}
+
+impl<'a> Line<'a> {
+    pub fn new(number: usize, text: &'a str) -> Line<'a> {
+        Line { number, text }
+    }
+
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.number, message)
+    }
+
+    /// Parses the whole (trimmed) line as a single value.
+    pub fn value<T: FromStr>(&self) -> ParseResult<T> {
+        parse_value(self.number, self.text.trim())
+    }
+
+    /// Every signed integer appearing in the line, ignoring whatever is around them.
+    pub fn ints(&self) -> ParseResult<Vec<i64>> {
+        let bytes = self.text.as_bytes();
+        let mut values: Vec<i64> = vec![];
+        let mut i = 0;
+        while i < bytes.len() {
+            let is_sign = bytes[i] == b'-' && i + 1 < bytes.len() && bytes[i + 1].is_ascii_digit();
+            if is_sign || bytes[i].is_ascii_digit() {
+                let start = i;
+                i += 1;
+                while i < bytes.len() && bytes[i].is_ascii_digit() {
+                    i += 1;
+                }
+                values.push(parse_value(self.number, &self.text[start..i])?);
+            } else {
+                i += 1;
+            }
+        }
+        Ok(values)
+    }
+
+    /// Values separated by runs of whitespace, such as columns aligned with spaces or tabs.
+    pub fn words<T: FromStr>(&self) -> ParseResult<Vec<T>> {
+        self.text.split_whitespace()
+            .map(|s| parse_value(self.number, s))
+            .collect()
+    }
+
+    /// Exactly `N` whitespace-separated columns.
+    pub fn columns<T: FromStr, const N: usize>(&self) -> ParseResult<[T; N]> {
+        let values: Vec<T> = self.words()?;
+        let found = values.len();
+        values.try_into().map_err(|_| self.error(format!("expected {} columns, found {}", N, found)))
+    }
+
+    /// Values delimited by `sep`, each trimmed before parsing.
+    pub fn separated<T: FromStr>(&self, sep: &str) -> ParseResult<Vec<T>> {
+        self.text.split(sep)
+            .map(|s| parse_value(self.number, s.trim()))
+            .collect()
+    }
+
+    /// A `key<sep>values` line such as `190: 10 19`, values being whitespace-separated.
+    pub fn key_values<K: FromStr, V: FromStr>(&self, sep: &str) -> ParseResult<(K, Vec<V>)> {
+        let (key, values) = self.text.split_once(sep)
+            .ok_or_else(|| self.error(format!("missing separator '{}'", sep)))?;
+        let key: K = parse_value(self.number, key.trim())?;
+        let values: Vec<V> = values.split_whitespace()
+            .map(|s| parse_value(self.number, s))
+            .collect::<ParseResult<Vec<V>>>()?;
+        Ok((key, values))
+    }
+}
+
+/// Types that can be read from a piece of puzzle input starting at line `line`.
+///
+/// Usually derived with `#[derive(FromInput)]`; see `aoc_derive` for the supported attributes.
+pub trait FromInput: Sized {
+    fn from_input(line: usize, text: &str) -> ParseResult<Self>;
+}
+
+macro_rules! from_input_via_from_str {
+    ($($t:ty),*) => {
+        $(impl FromInput for $t {
+            fn from_input(line: usize, text: &str) -> ParseResult<Self> {
+                parse_value(line, text.trim())
+            }
+        })*
+    };
+}
+
+from_input_via_from_str!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, char, String);
+
+/// Pieces of `text` delimited by `sep`, each read as a `T`. A blank `sep` splits on any run of whitespace.
+pub fn repeated<T: FromInput>(line: usize, text: &str, sep: &str) -> ParseResult<Vec<T>> {
+    if sep.trim().is_empty() {
+        text.split_whitespace().map(|s| T::from_input(line, s)).collect()
+    } else {
+        text.split(sep).map(|s| T::from_input(line, s)).collect()
+    }
+}
+
+/// Each line of `section` read as a `T`, `first_line` being the line `section` was numbered from.
+pub fn section_items<T: FromInput>(first_line: usize, section: &Section) -> ParseResult<Vec<T>> {
+    section.lines.iter()
+        .map(|l| T::from_input(first_line + l.number - 1, l.text))
+        .collect()
+}
+
+/// Reads every line of `input` as a `T`.
+pub fn parse_lines<T: FromInput>(input: &str) -> ParseResult<Vec<T>> {
+    lines(input)
+        .map(|line| T::from_input(line.number, line.text))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ints_finds_signed_numbers_anywhere() {
+        assert_eq!(Line::new(1, "mul(12,-3) x-y 4--5 -").ints(), Ok(vec![12, -3, 4, -5]));
+        assert_eq!(Line::new(1, "").ints(), Ok(vec![]));
+        assert_eq!(Line::new(7, "x 99999999999999999999").ints(), Err(ParseError::new(7, "invalid value '99999999999999999999'")));
+    }
+
+    #[test]
+    fn columns_and_words_split_on_whitespace() {
+        assert_eq!(Line::new(1, "3   4").columns::<i64, 2>(), Ok([3, 4]));
+        assert_eq!(Line::new(1, " -1\t2 ").columns::<i64, 2>(), Ok([-1, 2]));
+        assert_eq!(Line::new(2, "1 2 3").columns::<i64, 2>(), Err(ParseError::new(2, "expected 2 columns, found 3")));
+        assert_eq!(Line::new(3, "1 x").columns::<i64, 2>(), Err(ParseError::new(3, "invalid value 'x'")));
+        assert_eq!(Line::new(1, "  ").words::<i64>(), Ok(vec![]));
+    }
+
+    #[test]
+    fn separated_values_keep_empty_pieces() {
+        assert_eq!(Line::new(1, "1, 2,3").separated::<i32>(","), Ok(vec![1, 2, 3]));
+        // A trailing separator leaves an empty last value, which is an error rather than skipped.
+        assert_eq!(Line::new(4, "1,2,").separated::<i32>(","), Err(ParseError::new(4, "invalid value ''")));
+        assert_eq!(repeated::<i32>(5, "1 2 ", " "), Ok(vec![1, 2]));
+        assert_eq!(repeated::<i32>(5, "1,2,", ","), Err(ParseError::new(5, "invalid value ''")));
+    }
+
+    #[test]
+    fn key_values_need_the_separator() {
+        assert_eq!(Line::new(1, "190: 10 19").key_values::<i64, i64>(": "), Ok((190, vec![10, 19])));
+        assert_eq!(Line::new(1, "190: ").key_values::<i64, i64>(": "), Ok((190, vec![])));
+        assert_eq!(Line::new(6, "190 10").key_values::<i64, i64>(": "), Err(ParseError::new(6, "missing separator ': '")));
+    }
+
+    #[test]
+    fn lines_and_sections_keep_their_numbers() {
+        let input = "\n1|2\r\n3|4\n  \n\n5,6\n\n";
+        let numbered: Vec<(usize, &str)> = lines(input).map(|line| (line.number, line.text)).collect();
+        assert_eq!(numbered[1..3], [(2, "1|2"), (3, "3|4")]);
+        let sections = sections(input);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].lines, vec![Line::new(2, "1|2"), Line::new(3, "3|4")]);
+        assert_eq!((sections[1].first_line(), sections[1].lines.len()), (6, 1));
+        assert_eq!(Section { lines: vec![] }.first_line(), 0);
+        assert!(super::sections("\n \n").is_empty());
+    }
+
+    #[test]
+    fn errors_point_at_the_line_of_the_item() {
+        assert_eq!(parse_lines::<i32>("1\n2\nx\n"), Err(ParseError::new(3, "invalid value 'x'")));
+        // Lines of a section are numbered from where the section's text starts.
+        let sections = sections("1\n2\n\n3\ny\n");
+        assert_eq!(section_items::<i32>(1, &sections[1]), Err(ParseError::new(5, "invalid value 'y'")));
+        assert_eq!(section_items::<i32>(10, &sections[0]), Ok(vec![1, 2]));
+        assert_eq!(ParseError::new(3, "bad").to_string(), "line 3: bad");
+    }
+}
@@ -0,0 +1,231 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::process::ExitCode;
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::Duration;
+
+use crate::cli::Args;
+use crate::config::Config;
+use crate::days::{self, day_1, day_2};
+use crate::days::day_2::SafetyPolicy;
+use crate::explain::{self, Explanation, Format};
+use crate::parse::ParseResult;
+
+/// Contents of the input file at `path`, with the path in the error.
+pub fn read_input(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path, e))
+}
+
+/// Runs `task`, giving up once `timeout` has elapsed, `what` naming it in the error. The task keeps
+/// running in the background then, until the program exits.
+fn within<T: Send + 'static>(what: &str, timeout: Option<Duration>,
+                             task: impl FnOnce() -> T + Send + 'static) -> Result<T, String> {
+    let Some(timeout) = timeout else {
+        return Ok(task());
+    };
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || sender.send(task()));
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Ok(result),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(format!("{} timed out after {} s", what, timeout.as_secs())),
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(format!("{} panicked", what))
+    }
+}
+
+/// Solves `part` of `day` with `solve` within `timeout`.
+fn solve_within(day: u8, part: u8, solve: fn(&str) -> ParseResult<i64>, input: &Arc<str>,
+                input_path: &str, timeout: Option<Duration>) -> Result<i64, String> {
+    let input = Arc::clone(input);
+    within(&format!("day {} part {}", day, part), timeout, move || solve(&input))?
+        .map_err(|e| format!("{}:{}", input_path, e))
+}
+
+/// Solves both parts of `day` on the file at `input_path` and prints the answers.
+pub fn run(day: u8, input_path: &str, timeout: Option<Duration>) -> Result<(), String> {
+    let solution = days::solution(day).ok_or_else(|| format!("no solution for day {}", day))?;
+    let input: Arc<str> = read_input(input_path)?.into();
+    let part_1 = solve_within(day, 1, solution.part_1, &input, input_path, timeout)?;
+    println!("Day {} part 1: {}", day, part_1);
+    let part_2 = solve_within(day, 2, solution.part_2, &input, input_path, timeout)?;
+    println!("Day {} part 2: {}", day, part_2);
+    Ok(())
+}
+
+/// Solves both parts of `day` as `run` does, under the settings of `config`: its time limit, day
+/// 1's delimiter and day 2's safety policy when they aren't the puzzle's.
+pub fn run_with(day: u8, input_path: &str, config: &Config) -> Result<(), String> {
+    if day == 1 && config.day_1 != day_1::Delimiter::Whitespace {
+        run_day_1(input_path, &config.day_1, config.timeout(day))
+    } else if day == 2 && config.day_2 != SafetyPolicy::PUZZLE {
+        run_day_2(input_path, &config.day_2, config.timeout(day))
+    } else {
+        run(day, input_path, config.timeout(day))
+    }
+}
+
+/// Solves both parts of day 1 on the file at `input_path`, whose IDs are separated by `delimiter`,
+/// each within `timeout`.
+pub fn run_day_1(input_path: &str, delimiter: &day_1::Delimiter, timeout: Option<Duration>) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let lists = Arc::new(day_1::parse_with(&input, delimiter).map_err(|e| format!("{}:{}", input_path, e))?);
+    for part in [1, 2] {
+        let solve = if part == 1 { day_1::part_1 } else { day_1::part_2 };
+        let lists = Arc::clone(&lists);
+        let answer = within(&format!("day 1 part {}", part), timeout, move || solve(&lists))?;
+        println!("Day 1 part {}: {}", part, answer);
+    }
+    Ok(())
+}
+
+/// Solves both parts of day 2 on the file at `input_path` under `policy`, each within `timeout`.
+pub fn run_day_2(input_path: &str, policy: &SafetyPolicy, timeout: Option<Duration>) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let reports = Arc::new(day_2::parse(&input).map_err(|e| format!("{}:{}", input_path, e))?);
+    let policy = *policy;
+    for part in [1, 2] {
+        let solve = if part == 1 { day_2::part_1_with } else { day_2::part_2_with };
+        let reports = Arc::clone(&reports);
+        let answer = within(&format!("day 2 part {}", part), timeout, move || solve(&reports, &policy))?;
+        println!("Day 2 part {}: {}", part, answer);
+    }
+    Ok(())
+}
+
+/// Solves both parts of `day` and prints how each answer was found, in the format and under the
+/// time limit of `config`, and with day 2's safety policy.
+pub fn explain(day: u8, input_path: &str, config: &Config) -> Result<(), String> {
+    if day == 2 && config.day_2 != SafetyPolicy::PUZZLE {
+        return explain_day_2(input_path, &config.day_2, config.format);
+    }
+    let timeout = config.timeout(day);
+    let solution = days::solution(day).ok_or_else(|| format!("no solution for day {}", day))?;
+    let explain = solution.explain.ok_or_else(|| format!("day {} cannot explain its answers", day))?;
+    let input: Arc<str> = read_input(input_path)?.into();
+    let mut explanations: Vec<Explanation> = vec![];
+    for (part, solve) in [(1, solution.part_1), (2, solution.part_2)] {
+        let answer = solve_within(day, part, solve, &input, input_path, timeout)?;
+        let steps = explain(&input, part).map_err(|e| format!("{}:{}", input_path, e))?;
+        explanations.push(Explanation { day, part, answer, steps });
+    }
+    print!("{}", explain::render(&explanations, config.format));
+    Ok(())
+}
+
+/// Explains both parts of day 2 on the file at `input_path` under `policy`.
+fn explain_day_2(input_path: &str, policy: &SafetyPolicy, format: Format) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let reports = day_2::parse(&input).map_err(|e| format!("{}:{}", input_path, e))?;
+    let explanations: Vec<Explanation> = [(1, day_2::part_1_with(&reports, policy)), (2, day_2::part_2_with(&reports, policy))]
+        .into_iter()
+        .map(|(part, answer)| Explanation { day: 2, part, answer, steps: day_2::explain_with(&reports, policy, part) })
+        .collect();
+    print!("{}", explain::render(&explanations, format));
+    Ok(())
+}
+
+/// Solves day 1 reading the file at `input_path` line by line, for lists too large to fit in memory
+/// as a string. Beyond `memory_limit` IDs per list, they are sorted in runs on disk. Gives up once
+/// `timeout` has elapsed.
+pub fn run_day_1_streaming(input_path: &str, delimiter: &day_1::Delimiter, memory_limit: usize,
+                           timeout: Option<Duration>) -> Result<(), String> {
+    let file = fs::File::open(input_path).map_err(|e| format!("couldn't read {}: {}", input_path, e))?;
+    let reader = io::BufReader::with_capacity(1 << 20, file);
+    let delimiter = delimiter.clone();
+    let (part_1, part_2) = within("day 1", timeout, move || day_1::solve_streaming(reader, &delimiter, memory_limit))?
+        .map_err(|e| format!("{}:{}", input_path, e))?;
+    println!("Day 1 part 1: {}", part_1);
+    println!("Day 1 part 2: {}", part_2);
+    Ok(())
+}
+
+/// Compares day 1's lists from the file at `input_path` with each of `metrics`, each within `timeout`.
+pub fn run_day_1_metrics(input_path: &str, delimiter: &day_1::Delimiter, metrics: &[day_1::ListMetric],
+                         timeout: Option<Duration>) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let lists = Arc::new(day_1::read_lists(&input, delimiter).map_err(|e| format!("{}:{}", input_path, e))?);
+    for &metric in metrics {
+        let lists = Arc::clone(&lists);
+        match within(&format!("day 1 {}", metric), timeout, move || metric.measure(&lists.0, &lists.1))? {
+            Some(value) => println!("Day 1 {}: {}", metric, value),
+            None => return Err(format!("day 1 {} doesn't fit in 128 bits", metric))
+        }
+    }
+    Ok(())
+}
+
+/// Writes the breakdown of day 1's similarity score by ID for the file at `input_path`, built
+/// within `timeout`.
+pub fn run_day_1_report(input_path: &str, delimiter: &day_1::Delimiter, order: day_1::ReportOrder,
+                        format: day_1::ReportFormat, timeout: Option<Duration>) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let lists = day_1::parse_with(&input, delimiter).map_err(|e| format!("{}:{}", input_path, e))?;
+    let report = within("day 1 report", timeout, move || {
+        let mut report = day_1::similarity_report(&lists);
+        report.sort(order);
+        report
+    })?;
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    report.write(format, &mut out)
+        .and_then(|_| io::Write::flush(&mut out))
+        .map_err(|e| format!("couldn't write the report: {}", e))
+}
+
+/// Compares every pair of day 1's lists in the file at `input_path`, which may have any number of
+/// columns, within `timeout`.
+pub fn run_day_1_matrices(input_path: &str, delimiter: &day_1::Delimiter, timeout: Option<Duration>) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let lists = day_1::parse_many(&input, delimiter).map_err(|e| format!("{}:{}", input_path, e))?;
+    let matrices = within("day 1 matrices", timeout, move || {
+        let mut matrices: Vec<u8> = vec![];
+        day_1::write_matrices(&lists, &mut matrices).map(|_| matrices)
+    })?;
+    matrices.and_then(|matrices| io::Write::write_all(&mut io::stdout().lock(), &matrices))
+        .map_err(|e| format!("couldn't write the matrices: {}", e))
+}
+
+/// Counts day 2's reports made safe under `policy` by removing at most k levels, for each k up to
+/// `max_removals`, within `timeout`.
+pub fn run_day_2_removals(input_path: &str, policy: &SafetyPolicy, max_removals: usize,
+                          timeout: Option<Duration>) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let reports = day_2::parse(&input).map_err(|e| format!("{}:{}", input_path, e))?;
+    let policy = *policy;
+    let counts = within("day 2 removals", timeout, move || day_2::safe_counts(&reports, &policy, max_removals))?;
+    for (removals, count) in counts.into_iter().enumerate() {
+        println!("Day 2 safe with at most {} removals: {}", removals, count);
+    }
+    Ok(())
+}
+
+/// Writes why day 2's unsafe reports in the file at `input_path` are unsafe under `policy`, as a
+/// count of reports per first broken rule, found within `timeout`.
+pub fn run_day_2_violations(input_path: &str, policy: &SafetyPolicy, timeout: Option<Duration>) -> Result<(), String> {
+    let input = read_input(input_path)?;
+    let reports = day_2::parse(&input).map_err(|e| format!("{}:{}", input_path, e))?;
+    let policy = *policy;
+    let summary = within("day 2 violations", timeout, move || day_2::violation_summary(&reports, &policy))?;
+    let mut out = io::BufWriter::new(io::stdout().lock());
+    day_2::write_violation_summary(&summary, &mut out)
+        .and_then(|_| io::Write::flush(&mut out))
+        .map_err(|e| format!("couldn't write the summary: {}", e))
+}
+
+/// Entry point of the `day_N` binaries: runs `day` on the input named by the first argument, or on
+/// the default input of the settings.
+pub fn main(day: u8) -> ExitCode {
+    let raw_args: Vec<String> = env::args().skip(1).collect();
+    let result = Args::parse(&raw_args, &[]).and_then(|args| {
+        let config = Config::load(&args)?;
+        let variant = args.positional.first().unwrap_or(&config.input);
+        run_with(day, &config.input_path(day, variant), &config)
+    });
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
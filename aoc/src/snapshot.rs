@@ -0,0 +1,136 @@
+use std::fs;
+
+use crate::config::Config;
+use crate::days::{day_6, day_8};
+use crate::parse::ParseResult;
+use crate::runner::read_input;
+
+/*
+    Snapshot tests for rendered output. Each snapshot renders one of a day's inputs and is compared
+    with a file checked in next to it, `data/day_N/<name>.snap`. After an intended change to a
+    renderer, `aoc snapshot --accept` rewrites the files, and the diff shows up in review. Both the
+    inputs and the snapshots are looked up in the data directory of the settings.
+*/
+
+/// A rendering of a day's input, compared with a checked-in file.
+pub struct Snapshot {
+    pub day: u8,
+    pub name: &'static str,
+    /// Input variant the rendering is made from, as in `aoc run <day> <input>`.
+    pub input: &'static str,
+    pub render: fn(&str) -> ParseResult<String>
+}
+
+macro_rules! snapshot {
+    ($day:literal, $name:literal, $input:literal, $module:ident, $render:ident) => {
+        Snapshot {
+            day: $day,
+            name: $name,
+            input: $input,
+            render: |input| {
+                let mut out: Vec<u8> = vec![];
+                $module::$render(&$module::parse(input)?, &mut out).expect("writing to memory doesn't fail");
+                Ok(String::from_utf8(out).expect("renderers write UTF-8"))
+            }
+        }
+    };
+}
+
+pub const SNAPSHOTS: [Snapshot; 3] = [
+    snapshot!(6, "path_test", "test", day_6, render_part_1),
+    snapshot!(8, "antinodes_test", "test", day_8, render_part_1),
+    snapshot!(8, "resonant_antinodes_test", "test", day_8, render_part_2)
+];
+
+/// The snapshots of `day`, or of every day.
+pub fn snapshots(day: Option<u8>) -> impl Iterator<Item = &'static Snapshot> {
+    SNAPSHOTS.iter().filter(move |snapshot| day.is_none_or(|day| snapshot.day == day))
+}
+
+impl Snapshot {
+    pub fn path(&self, config: &Config) -> String {
+        format!("{}/day_{}/{}.snap", config.data_dir, self.day, self.name)
+    }
+
+    /// Renders the snapshot's input as it is now.
+    pub fn rendered(&self, config: &Config) -> Result<String, String> {
+        let path = config.input_path(self.day, self.input);
+        let input = read_input(&path)?;
+        (self.render)(&input).map_err(|e| format!("{}:{}", path, e))
+    }
+
+    /// Compares the rendering with the snapshot file, describing any difference with a line diff.
+    pub fn check(&self, config: &Config) -> Result<(), String> {
+        let actual = self.rendered(config)?;
+        let path = self.path(config);
+        let expected = fs::read_to_string(&path)
+            .map_err(|e| format!("couldn't read {}: {} (`aoc snapshot --accept` creates it)", path, e))?;
+        if actual == expected {
+            return Ok(());
+        }
+        let diff = line_diff(&expected, &actual);
+        if diff.lines().all(|line| line.starts_with(' ')) {
+            Err(format!("{} doesn't match the rendering: only the line endings differ", path))
+        } else {
+            Err(format!("{} doesn't match the rendering (- snapshot, + rendering):\n{}", path, diff))
+        }
+    }
+
+    /// Writes the current rendering to the snapshot file. Returns whether the file changed.
+    pub fn accept(&self, config: &Config) -> Result<bool, String> {
+        let actual = self.rendered(config)?;
+        let path = self.path(config);
+        if fs::read_to_string(&path).is_ok_and(|expected| expected == actual) {
+            return Ok(false);
+        }
+        fs::write(&path, actual).map_err(|e| format!("couldn't write {}: {}", path, e))?;
+        Ok(true)
+    }
+}
+
+/// Line diff from `old` to `new` based on their longest common subsequence of lines. Lines only in
+/// `old` start with `-`, lines only in `new` with `+`, and common lines with a space.
+pub fn line_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+    // common[i][j]: length of the longest common subsequence of old[i..] and new[j..].
+    let mut common = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            common[i][j] = if old[i] == new[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+    let mut diff = String::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            diff += &format!("  {}\n", old[i]);
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && common[i + 1][j] >= common[i][j + 1]) {
+            diff += &format!("- {}\n", old[i]);
+            i += 1;
+        } else {
+            diff += &format!("+ {}\n", new[j]);
+            j += 1;
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_diff_marks_removed_and_added_lines() {
+        let diff = line_diff("a\nb\nc\nd\n", "a\nc\nx\nd\n");
+        assert_eq!(diff, "  a\n- b\n  c\n+ x\n  d\n");
+        assert_eq!(line_diff("", "a\n"), "+ a\n");
+        assert_eq!(line_diff("a\n", ""), "- a\n");
+    }
+}
@@ -1,20 +1,25 @@
-use std::env;
 use std::fs::File;
 use std::io::prelude::*;
 use std::path::Path;
 
-fn get_path(day_number: i8, is_test: bool) -> String {
-    let input_file: &str = if is_test { "test.txt" } else { "input.txt" };
-    "data/day_".to_string() + &day_number.to_string() + "/" + input_file
+use crate::config::DEFAULT_DATA_DIR;
+
+/// Path of a day's input: `variant` names a file of the day's data directory, such as `test`,
+/// `input` or a generated `gen_1_1000`, unless it already is a path.
+pub fn input_path(day_number: u8, variant: &str) -> String {
+    input_path_in(DEFAULT_DATA_DIR, day_number, variant)
 }
 
-pub fn get_input_path(day_number: i8) -> String {
-    let args: Vec<String> = env::args().collect();
-    let is_test = args.len() > 1_usize && args[1].eq("test");
-    get_path(day_number, is_test)
+/// Same as `input_path`, with the data directory at `data_dir`.
+pub fn input_path_in(data_dir: &str, day_number: u8, variant: &str) -> String {
+    if variant.contains('/') || variant.ends_with(".txt") {
+        variant.to_string()
+    } else {
+        format!("{}/day_{}/{}.txt", data_dir, day_number, variant)
+    }
 }
 
-pub fn vec_to_string(vec: &Vec<i32>) -> String {
+pub fn vec_to_string(vec: &[i32]) -> String {
     let vec_of_str: Vec<String> = vec.iter().map(|i| i.to_string()).collect();
     vec_of_str.join(",")
 }
@@ -0,0 +1,24 @@
+use aoc::cli::Args;
+use aoc::config::Config;
+use aoc::cross_check::{assert_agree, file_inputs, generated_inputs, VARIANTS};
+
+// Small generated inputs keep the reference implementations fast enough for debug builds.
+const SIZE: usize = 20;
+
+#[test]
+fn variants_agree_on_examples() {
+    let config = Config::resolve(None, |_| None, &Args::parse(&[], &[]).unwrap()).unwrap();
+    for variant in &VARIANTS {
+        let inputs: Vec<_> = file_inputs(&config, variant.day).into_iter()
+            .filter(|input| input.name == config.input_path(variant.day, "test"))
+            .collect();
+        assert_agree(variant, &inputs);
+    }
+}
+
+#[test]
+fn variants_agree_on_generated_inputs() {
+    for variant in &VARIANTS {
+        assert_agree(variant, &generated_inputs(variant.day, 0..5, SIZE));
+    }
+}
@@ -0,0 +1,71 @@
+use aoc::parse::{FromInput, ParseError};
+use aoc::FromInput;
+
+#[derive(FromInput, Debug, PartialEq, Eq)]
+#[input(sep = " -> ")]
+struct Move {
+    from: String,
+    to: String,
+    #[input(sep = ",", non_empty)]
+    amounts: Vec<u32>
+}
+
+#[derive(FromInput, Debug, PartialEq, Eq)]
+struct Pair(i64, i64);
+
+#[derive(FromInput, Debug, PartialEq, Eq)]
+#[input(sections)]
+struct Sections {
+    pairs: Vec<Pair>,
+    moves: Vec<Move>
+}
+
+#[test]
+fn fields_are_split_on_the_struct_separator() {
+    assert_eq!(Move::from_input(3, "a -> b -> 1,2").unwrap(),
+               Move { from: "a".to_string(), to: "b".to_string(), amounts: vec![1, 2] });
+    assert_eq!(Pair::from_input(1, "-4 7").unwrap(), Pair(-4, 7));
+    // The last field takes the rest of the line.
+    assert_eq!(Pair::from_input(1, "1 2 3").unwrap_err(), ParseError::new(1, "invalid value '2 3'"));
+}
+
+#[test]
+fn errors_point_at_the_line() {
+    assert_eq!(Move::from_input(3, "a -> b").unwrap_err(),
+               ParseError::new(3, "expected 3 fields separated by ' -> '"));
+    assert_eq!(Move::from_input(4, "a -> b -> 1,x").unwrap_err(), ParseError::new(4, "invalid value 'x'"));
+    assert_eq!(Move::from_input(5, "a -> b -> ").unwrap_err(), ParseError::new(5, "invalid value ''"));
+}
+
+#[test]
+fn non_empty_fields_need_a_value() {
+    #[derive(FromInput, Debug)]
+    #[input(sep = ":")]
+    struct Key {
+        _name: String,
+        #[input(sep = " ", non_empty)]
+        values: Vec<u8>
+    }
+    assert_eq!(Key::from_input(2, "k: ").unwrap_err(), ParseError::new(2, "expected at least one value for values"));
+    assert_eq!(Key::from_input(2, "k: 1 2").unwrap().values, vec![1, 2]);
+}
+
+#[test]
+fn sections_hold_one_item_per_line() {
+    let parsed = Sections::from_input(1, "1 2\n3 4\n\n\nx -> y -> 5\n").unwrap();
+    assert_eq!(parsed.pairs, vec![Pair(1, 2), Pair(3, 4)]);
+    assert_eq!(parsed.moves, vec![Move { from: "x".to_string(), to: "y".to_string(), amounts: vec![5] }]);
+    assert_eq!(Sections::from_input(1, "1 2\n\n3 4\n\n5 6\n").unwrap_err(),
+               ParseError::new(1, "expected 2 sections, found 3"));
+    assert_eq!(Sections::from_input(1, "1 2\n\nx -> y\n").unwrap_err().line, 3);
+}
+
+#[test]
+fn tuple_structs_take_sections_in_order() {
+    #[derive(FromInput, Debug)]
+    #[input(sections)]
+    struct Input(Vec<String>, Vec<u8>);
+    let Input(names, values) = Input::from_input(1, "a b\nc\n\n1\n2\n").unwrap();
+    assert_eq!((names, values), (vec!["a b".to_string(), "c".to_string()], vec![1, 2]));
+    assert_eq!(Input::from_input(1, "a\n\n300\n").unwrap_err(), ParseError::new(3, "invalid value '300'"));
+}
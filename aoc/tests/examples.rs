@@ -0,0 +1,51 @@
+use aoc::days::solution;
+use aoc::utils::{input_path, read_contents};
+
+/// Runs both parts of `day` on its `test.txt` and compares them with the expected answers.
+fn check_example(day: u8, part_1: i64, part_2: i64) {
+    let input = read_contents(&input_path(day, "test"));
+    let solution = solution(day).unwrap();
+    assert_eq!((solution.part_1)(&input).unwrap(), part_1, "day {} part 1", day);
+    assert_eq!((solution.part_2)(&input).unwrap(), part_2, "day {} part 2", day);
+}
+
+#[test]
+fn day_1() {
+    check_example(1, 11, 31);
+}
+
+// test.txt is the published example followed by extra edge cases, see day_2's unit tests.
+#[test]
+fn day_2() {
+    check_example(2, 2, 9);
+}
+
+#[test]
+fn day_3() {
+    check_example(3, 161, 48);
+}
+
+#[test]
+fn day_4() {
+    check_example(4, 18, 9);
+}
+
+#[test]
+fn day_5() {
+    check_example(5, 143, 123);
+}
+
+#[test]
+fn day_6() {
+    check_example(6, 41, 6);
+}
+
+#[test]
+fn day_7() {
+    check_example(7, 3749, 11387);
+}
+
+#[test]
+fn day_8() {
+    check_example(8, 14, 34);
+}
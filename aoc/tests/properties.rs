@@ -0,0 +1,160 @@
+use std::collections::BTreeSet;
+
+use proptest::prelude::*;
+use proptest::collection::vec;
+use proptest::sample::subsequence;
+
+use aoc::days::{day_1, day_2, day_4, day_5, day_7};
+
+/*
+    Strategies generating valid inputs for each day, in the formats the parsers accept (e.g. any
+    whitespace between day 1's columns), and invariants that must hold on any of them.
+*/
+
+/// IDs up to 10^15, which keeps the answers within 64 bits for up to 50 lines.
+fn day_1_input() -> impl Strategy<Value = String> {
+    let separator = prop::string::string_regex("[ \t]{1,4}").unwrap();
+    vec((-1_000_000_000_000_000..1_000_000_000_000_000i64, separator, 0..1_000_000_000_000_000i64), 1..50)
+        .prop_map(|pairs| pairs.iter().map(|(left, sep, right)| format!("{}{}{}\n", left, sep, right)).collect())
+}
+
+/// Reports of 3 to 9 levels moving by small steps, so that safe and nearly safe ones are common.
+fn day_2_input() -> impl Strategy<Value = String> {
+    vec((1..100i32, vec(-4..=4i32, 2..9)), 1..30)
+        .prop_map(|reports| reports.iter()
+            .map(|(first, steps)| {
+                let levels: Vec<String> = std::iter::once(*first)
+                    .chain(steps.iter().scan(*first, |level, step| { *level += step; Some(*level) }))
+                    .map(|level| level.to_string())
+                    .collect();
+                levels.join(" ") + "\n"
+            })
+            .collect())
+}
+
+fn day_4_input() -> impl Strategy<Value = String> {
+    (1..12usize, 1..12usize)
+        .prop_flat_map(|(n_rows, n_cols)| vec(vec(prop::sample::select(vec!['X', 'M', 'A', 'S']), n_cols), n_rows))
+        .prop_map(|rows| rows.iter().map(|row| row.iter().collect::<String>()).collect::<Vec<String>>().join("\n"))
+}
+
+/// Rules that are consistent with a hidden order of the pages, and updates drawn from those pages.
+fn day_5_input() -> impl Strategy<Value = String> {
+    subsequence((10..100).collect::<Vec<i32>>(), 2..15)
+        .prop_shuffle()
+        .prop_flat_map(|order| {
+            let n = order.len();
+            let kept_rules = vec(any::<bool>(), n * (n - 1) / 2);
+            let updates = vec(subsequence(order.clone(), 1..=n).prop_shuffle(), 1..10);
+            (Just(order), kept_rules, updates)
+        })
+        .prop_map(|(order, kept_rules, updates)| {
+            let pairs = (0..order.len()).flat_map(|i| (i + 1..order.len()).map(move |j| (i, j)));
+            // Always keep the first rule so that the rules section is never empty.
+            let mut text: String = pairs.zip(kept_rules).enumerate()
+                .filter(|&(index, (_, kept))| index == 0 || kept)
+                .map(|(_, ((i, j), _))| format!("{}|{}\n", order[i], order[j]))
+                .collect();
+            text += "\n";
+            for update in updates {
+                text += &update.iter().map(|page| page.to_string()).collect::<Vec<String>>().join(",");
+                text += "\n";
+            }
+            text
+        })
+}
+
+/// Equations with small terms, half of them built by applying random operators so they are solvable.
+fn day_7_input() -> impl Strategy<Value = String> {
+    vec((vec(1..100i64, 1..7), vec(0..3u8, 6), any::<bool>(), 1..10_000i64), 1..20)
+        .prop_map(|equations| equations.iter()
+            .map(|(terms, operators, solvable, random_result)| {
+                let result = if *solvable {
+                    terms[1..].iter().zip(operators).fold(terms[0], |acc, (term, operator)| match operator {
+                        0 => acc + term,
+                        1 => acc * term,
+                        _ => format!("{}{}", acc, term).parse().unwrap()
+                    })
+                } else {
+                    *random_result
+                };
+                let terms: Vec<String> = terms.iter().map(|term| term.to_string()).collect();
+                format!("{}: {}\n", result, terms.join(" "))
+            })
+            .collect())
+}
+
+proptest! {
+    #[test]
+    fn day_1_round_trips(input in day_1_input()) {
+        let lists = day_1::parse(&input).unwrap();
+        prop_assert_eq!(day_1::parse(&day_1::format(&lists)).unwrap(), lists);
+    }
+
+    #[test]
+    fn day_1_metrics_generalize_the_parts(input in day_1_input()) {
+        use day_1::{ListMetric, MetricValue};
+        let lists = day_1::parse(&input).unwrap();
+        let (left, right) = day_1::read_lists(&input, &day_1::Delimiter::Whitespace).unwrap();
+        prop_assert_eq!(ListMetric::Distance.measure(&left, &right), Some(MetricValue::Integer(day_1::part_1(&lists) as i128)));
+        prop_assert_eq!(ListMetric::Similarity.measure(&left, &right), Some(MetricValue::Integer(day_1::part_2(&lists) as i128)));
+        prop_assert_eq!(ListMetric::KendallTau.measure(&left, &left), Some(MetricValue::Integer(0)));
+    }
+
+    #[test]
+    fn day_1_online_lists_match_the_parts(input in day_1_input()) {
+        let lists = day_1::parse(&input).unwrap();
+        let online = day_1::OnlineLists::from_lists(&lists);
+        prop_assert_eq!(online.distance(), day_1::part_1(&lists) as i128);
+        prop_assert_eq!(online.similarity(), day_1::part_2(&lists) as i128);
+    }
+
+    #[test]
+    fn day_1_inputs_pass_their_checks(input in day_1_input()) {
+        prop_assert_eq!(day_1::check_input(&input), vec![]);
+    }
+
+    #[test]
+    fn day_2_round_trips(input in day_2_input()) {
+        let reports = day_2::parse(&input).unwrap();
+        prop_assert_eq!(&day_2::format(&reports), &input);
+        prop_assert_eq!(day_2::parse(&day_2::format(&reports)).unwrap(), reports);
+    }
+
+    #[test]
+    fn day_2_tolerance_only_adds_safe_reports(input in day_2_input()) {
+        let reports = day_2::parse(&input).unwrap();
+        prop_assert!(day_2::part_2(&reports) >= day_2::part_1(&reports));
+    }
+
+    #[test]
+    fn day_4_round_trips(input in day_4_input()) {
+        let grid = day_4::parse(&input).unwrap();
+        prop_assert_eq!(&day_4::format(&grid), &input);
+    }
+
+    #[test]
+    fn day_5_round_trips(input in day_5_input()) {
+        // Rules come out grouped by page, so compare them as a set.
+        let rules = |queue: &day_5::PrintQueue| -> BTreeSet<(i32, i32)> {
+            queue.0.edges().map(|(&before, &after)| (before, after)).collect()
+        };
+        let queue = day_5::parse(&input).unwrap();
+        let reparsed = day_5::parse(&day_5::format(&queue)).unwrap();
+        prop_assert_eq!(rules(&reparsed), rules(&queue));
+        prop_assert_eq!(&reparsed.1, &queue.1);
+    }
+
+    #[test]
+    fn day_7_round_trips(input in day_7_input()) {
+        let equations = day_7::parse(&input).unwrap();
+        prop_assert_eq!(&day_7::format(&equations), &input);
+        prop_assert_eq!(day_7::parse(&day_7::format(&equations)).unwrap(), equations);
+    }
+
+    #[test]
+    fn day_7_concatenation_only_adds_solvable_equations(input in day_7_input()) {
+        let equations = day_7::parse(&input).unwrap();
+        prop_assert!(day_7::part_2(&equations) >= day_7::part_1(&equations));
+    }
+}
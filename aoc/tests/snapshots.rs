@@ -0,0 +1,10 @@
+use aoc::cli::Args;
+use aoc::config::Config;
+use aoc::snapshot::SNAPSHOTS;
+
+#[test]
+fn renderings_match_snapshots() {
+    let config = Config::resolve(None, |_| None, &Args::parse(&[], &[]).unwrap()).unwrap();
+    let mismatches: Vec<String> = SNAPSHOTS.iter().filter_map(|snapshot| snapshot.check(&config).err()).collect();
+    assert!(mismatches.is_empty(), "{}", mismatches.join("\n"));
+}